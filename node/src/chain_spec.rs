@@ -9,7 +9,7 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 use sp_std::vec::Vec;
 use subauction_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, OrmlNftConfig, Runtime, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	SystemConfig, TokensConfig, WASM_BINARY,
 };
 
 // The URL for the telemetry server.
@@ -157,6 +157,10 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		}),
+		orml_tokens: Some(TokensConfig {
+			// Endow the accounts with token 1 as well, to bid in auctions held in it
+			endowed_accounts: endowed_accounts.iter().cloned().map(|k| (k, 1, 1 << 60)).collect(),
+		}),
 		orml_nft: Some(OrmlNftConfig {
 			tokens: create_testnet_tokens(&endowed_accounts),
 		}),
@@ -5,14 +5,20 @@
 
 #![warn(missing_docs)]
 
+mod auction;
+
 use std::sync::Arc;
 
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_transaction_pool::TransactionPool;
-use subauction_runtime::{opaque::Block, AccountId, Balance, Index};
+use subauction_runtime::{
+	opaque::Block, AccountId, AuctionId, Balance, BlockNumber, ClassId, CurrencyId, Index, TokenId,
+};
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -22,19 +28,34 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Executor of the subscription tasks
+	pub subscription_executor: SubscriptionTaskExecutor,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
+pub fn create_full<C, P, B>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
 where
-	C: ProvideRuntimeApi<Block>,
+	B: Backend<Block> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + BlockchainEvents<Block> + StorageProvider<Block, B>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_auction_rpc::AuctionRuntimeApi<
+		Block,
+		AccountId,
+		AuctionId,
+		Balance,
+		BlockNumber,
+		ClassId,
+		TokenId,
+		CurrencyId,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use auction::{AuctionEvents, AuctionEventsApi};
+	use pallet_auction_rpc::{Auction, AuctionApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -43,6 +64,7 @@ where
 		client,
 		pool,
 		deny_unsafe,
+		subscription_executor,
 	} = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(
@@ -51,12 +73,16 @@ where
 		deny_unsafe,
 	)));
 
-	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client)));
+	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
+		client.clone(),
+	)));
+
+	io.extend_with(AuctionApi::to_delegate(Auction::new(client.clone())));
 
-	// Extend this RPC with a custom API by using the following syntax.
-	// `YourRpcStruct` should have a reference to a client, which is needed
-	// to call into the runtime.
-	// `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
+	io.extend_with(AuctionEventsApi::to_delegate(AuctionEvents::new(
+		client,
+		subscription_executor,
+	)));
 
 	io
 }
@@ -0,0 +1,287 @@
+//! Subscriptions streaming the auction events of imported blocks as typed JSON.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use frame_system::EventRecord;
+use futures::{StreamExt, TryStreamExt};
+use jsonrpc_core::{
+	futures::{Future, Sink},
+	Error as RpcError, Result as RpcResult,
+};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use log::warn;
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_rpc::{Metadata, SubscriptionTaskExecutor};
+use serde::Serialize;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::generic::BlockId;
+use subauction_runtime::{opaque::Block, pallet_auction, AccountId, AuctionId, Balance, BlockNumber, Event, Hash};
+
+/// Auction event of an imported block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum AuctionEvent {
+	/// Auction was created by the owner
+	#[serde(rename_all = "camelCase")]
+	AuctionCreated { auction_id: AuctionId, owner: AccountId },
+	/// Bid was placed
+	#[serde(rename_all = "camelCase")]
+	Bid {
+		auction_id: AuctionId,
+		bidder: AccountId,
+		amount: Balance,
+	},
+	/// Late bid or the owner postponed the end of the auction
+	#[serde(rename_all = "camelCase")]
+	AuctionExtended { auction_id: AuctionId, end: BlockNumber },
+	/// Terms of the auction were changed by its owner
+	#[serde(rename_all = "camelCase")]
+	AuctionUpdated { auction_id: AuctionId },
+	/// Auction ended, the owner received `proceeds` and `commission` went to the fee destination
+	#[serde(rename_all = "camelCase")]
+	AuctionConcluded {
+		auction_id: AuctionId,
+		proceeds: Balance,
+		commission: Balance,
+	},
+	/// Auction ended without a winner or below its reserve price, the token stays with the owner
+	#[serde(rename_all = "camelCase")]
+	AuctionConcludedWithoutSale { auction_id: AuctionId },
+	/// Token was bought outright, the owner received `proceeds` and `commission` went to the fee destination
+	#[serde(rename_all = "camelCase")]
+	AuctionSold {
+		auction_id: AuctionId,
+		buyer: AccountId,
+		proceeds: Balance,
+		commission: Balance,
+	},
+	/// English auction was ended by paying its buy now price
+	#[serde(rename_all = "camelCase")]
+	AuctionBoughtNow {
+		auction_id: AuctionId,
+		buyer: AccountId,
+		proceeds: Balance,
+		commission: Balance,
+	},
+	/// Running auction was cancelled by its owner, the leading bidder received `penalty`
+	#[serde(rename_all = "camelCase")]
+	AuctionCancelled { auction_id: AuctionId, penalty: Balance },
+	/// Auction was removed before it ended
+	#[serde(rename_all = "camelCase")]
+	AuctionRemoved { auction_id: AuctionId },
+	/// Failed settlement of the auction was given up and every bid refunded
+	#[serde(rename_all = "camelCase")]
+	SettlementAborted { auction_id: AuctionId },
+}
+
+impl AuctionEvent {
+	/// Typed counterpart of a runtime event, if it is one of the streamed auction events
+	fn from_runtime_event(event: Event) -> Option<Self> {
+		use pallet_auction::Event as PalletEvent;
+
+		match event {
+			Event::pallet_auction(PalletEvent::AuctionCreated(owner, auction_id)) => {
+				Some(Self::AuctionCreated { auction_id, owner })
+			}
+			Event::pallet_auction(PalletEvent::Bid(auction_id, bidder, amount)) => Some(Self::Bid {
+				auction_id,
+				bidder,
+				amount,
+			}),
+			Event::pallet_auction(PalletEvent::AuctionExtended(auction_id, end)) => {
+				Some(Self::AuctionExtended { auction_id, end })
+			}
+			Event::pallet_auction(PalletEvent::AuctionUpdated(auction_id)) => Some(Self::AuctionUpdated { auction_id }),
+			Event::pallet_auction(PalletEvent::AuctionConcluded(auction_id, proceeds, commission)) => {
+				Some(Self::AuctionConcluded {
+					auction_id,
+					proceeds,
+					commission,
+				})
+			}
+			Event::pallet_auction(PalletEvent::AuctionConcludedWithoutSale(auction_id)) => {
+				Some(Self::AuctionConcludedWithoutSale { auction_id })
+			}
+			Event::pallet_auction(PalletEvent::AuctionSold(auction_id, buyer, proceeds, commission)) => {
+				Some(Self::AuctionSold {
+					auction_id,
+					buyer,
+					proceeds,
+					commission,
+				})
+			}
+			Event::pallet_auction(PalletEvent::AuctionBoughtNow(auction_id, buyer, proceeds, commission)) => {
+				Some(Self::AuctionBoughtNow {
+					auction_id,
+					buyer,
+					proceeds,
+					commission,
+				})
+			}
+			Event::pallet_auction(PalletEvent::AuctionCancelled(auction_id, penalty)) => {
+				Some(Self::AuctionCancelled { auction_id, penalty })
+			}
+			Event::pallet_auction(PalletEvent::AuctionRemoved(auction_id)) => Some(Self::AuctionRemoved { auction_id }),
+			Event::pallet_auction(PalletEvent::SettlementAborted(auction_id)) => {
+				Some(Self::SettlementAborted { auction_id })
+			}
+			_ => None,
+		}
+	}
+
+	/// Auction the event belongs to
+	fn auction_id(&self) -> AuctionId {
+		match *self {
+			Self::AuctionCreated { auction_id, .. }
+			| Self::Bid { auction_id, .. }
+			| Self::AuctionExtended { auction_id, .. }
+			| Self::AuctionUpdated { auction_id }
+			| Self::AuctionConcluded { auction_id, .. }
+			| Self::AuctionConcludedWithoutSale { auction_id }
+			| Self::AuctionSold { auction_id, .. }
+			| Self::AuctionBoughtNow { auction_id, .. }
+			| Self::AuctionCancelled { auction_id, .. }
+			| Self::AuctionRemoved { auction_id }
+			| Self::SettlementAborted { auction_id } => auction_id,
+		}
+	}
+}
+
+/// Auction event together with the block it was emitted in
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuctionNotification {
+	/// Best block which emitted the event
+	pub block_hash: Hash,
+	#[serde(flatten)]
+	pub event: AuctionEvent,
+}
+
+#[rpc(server)]
+pub trait AuctionEventsApi {
+	/// RPC metadata
+	type Metadata;
+
+	/// Stream the bids placed in all auctions
+	#[pubsub(subscription = "auction_bids", subscribe, name = "auction_subscribeBids")]
+	fn subscribe_bids(&self, metadata: Self::Metadata, subscriber: Subscriber<AuctionNotification>);
+
+	/// Stop streaming bids
+	#[pubsub(subscription = "auction_bids", unsubscribe, name = "auction_unsubscribeBids")]
+	fn unsubscribe_bids(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool>;
+
+	/// Stream the creation, bids, updates, end extensions and the end of auction `id`, however it ends
+	#[pubsub(subscription = "auction_auction", subscribe, name = "auction_subscribeAuction")]
+	fn subscribe_auction(&self, metadata: Self::Metadata, subscriber: Subscriber<AuctionNotification>, id: AuctionId);
+
+	/// Stop streaming the events of an auction
+	#[pubsub(subscription = "auction_auction", unsubscribe, name = "auction_unsubscribeAuction")]
+	fn unsubscribe_auction(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool>;
+}
+
+/// Implements the `AuctionEventsApi` RPC trait by decoding the events of every new best block
+pub struct AuctionEvents<C, B> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> AuctionEvents<C, B> {
+	/// Create new `AuctionEvents` spawning its subscriptions on the given executor
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self {
+			client,
+			manager: SubscriptionManager::new(Arc::new(executor)),
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, B> AuctionEvents<C, B>
+where
+	B: Backend<Block> + Send + Sync + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+{
+	/// Stream the auction events of new best blocks accepted by `filter` to the subscriber
+	fn subscribe<F>(&self, subscriber: Subscriber<AuctionNotification>, filter: F)
+	where
+		F: Fn(&AuctionEvent) -> bool + Send + 'static,
+	{
+		let client = self.client.clone();
+		let notifications = self
+			.client
+			.import_notification_stream()
+			.filter(|notification| futures::future::ready(notification.is_new_best))
+			.flat_map(move |notification| {
+				let block_hash = notification.hash;
+				let events = block_events(&*client, block_hash)
+					.into_iter()
+					.filter(|event| filter(event))
+					.map(move |event| AuctionNotification { block_hash, event })
+					.collect::<Vec<_>>();
+				futures::stream::iter(events)
+			})
+			.map(|notification| Ok::<_, ()>(Ok::<_, RpcError>(notification)))
+			.compat();
+
+		self.manager.add(subscriber, |sink| {
+			sink.sink_map_err(|e| warn!("Error sending auction notifications: {:?}", e))
+				.send_all(notifications)
+				.map(|_| ())
+		});
+	}
+}
+
+impl<C, B> AuctionEventsApi for AuctionEvents<C, B>
+where
+	B: Backend<Block> + Send + Sync + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+{
+	type Metadata = Metadata;
+
+	fn subscribe_bids(&self, _metadata: Self::Metadata, subscriber: Subscriber<AuctionNotification>) {
+		self.subscribe(subscriber, |event| matches!(event, AuctionEvent::Bid { .. }))
+	}
+
+	fn unsubscribe_bids(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
+		Ok(self.manager.cancel(id))
+	}
+
+	fn subscribe_auction(&self, _metadata: Self::Metadata, subscriber: Subscriber<AuctionNotification>, id: AuctionId) {
+		self.subscribe(subscriber, move |event| event.auction_id() == id)
+	}
+
+	fn unsubscribe_auction(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
+
+/// Auction events emitted in block `hash`, empty if the events can't be read or decoded
+fn block_events<C, B>(client: &C, hash: Hash) -> Vec<AuctionEvent>
+where
+	B: Backend<Block>,
+	C: StorageProvider<Block, B>,
+{
+	let mut key = twox_128(b"System").to_vec();
+	key.extend_from_slice(&twox_128(b"Events"));
+	let data = match client.storage(&BlockId::hash(hash), &StorageKey(key)) {
+		Ok(Some(data)) => data,
+		Ok(None) => return Vec::new(),
+		Err(e) => {
+			warn!("Unable to read the events of block {}: {:?}", hash, e);
+			return Vec::new();
+		}
+	};
+	match Vec::<EventRecord<Event, Hash>>::decode(&mut &data.0[..]) {
+		Ok(records) => records
+			.into_iter()
+			.filter_map(|record| AuctionEvent::from_runtime_event(record.event))
+			.collect(),
+		Err(e) => {
+			warn!("Unable to decode the events of block {}: {:?}", hash, e);
+			Vec::new()
+		}
+	}
+}
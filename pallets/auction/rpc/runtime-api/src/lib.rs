@@ -0,0 +1,47 @@
+//! Runtime API for querying auctions without decoding raw storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use pallet_auction::{AuctionInfo, BidRecord};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionApi<AccountId, AuctionId, Balance, BlockNumber, ClassId, TokenId, CurrencyId> where
+		AccountId: Codec,
+		AuctionId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		ClassId: Codec,
+		TokenId: Codec,
+		CurrencyId: Codec,
+	{
+		/// Auction `id`, if it exists
+		fn auction(id: AuctionId) -> Option<AuctionInfo<AccountId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>>;
+
+		/// Auctions which have not been concluded yet among the `count` IDs from `start` on
+		fn active_auctions(
+			start: AuctionId,
+			count: u32,
+		) -> Vec<(AuctionId, AuctionInfo<AccountId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>)>;
+
+		/// Lowest bid auction `id` accepts next, if it takes bids
+		fn minimal_next_bid(id: AuctionId) -> Option<Balance>;
+
+		/// Price at which the token of auction `id` can be bought right now, if it is for sale
+		fn current_price(id: AuctionId) -> Option<Balance>;
+
+		/// Number of blocks until auction `id` stops taking bids, if it ever does
+		fn time_remaining(id: AuctionId) -> Option<BlockNumber>;
+
+		/// IDs of the auctions of `owner`
+		fn auctions_by_owner(owner: AccountId) -> Vec<AuctionId>;
+
+		/// Auction in which the token is listed, if any
+		fn auction_by_token(class_id: ClassId, token_id: TokenId) -> Option<AuctionId>;
+
+		/// Most recent bids of auction `id`, oldest first
+		fn bid_history(id: AuctionId) -> Vec<BidRecord<AccountId, Balance, BlockNumber>>;
+	}
+}
@@ -0,0 +1,203 @@
+//! RPC methods for querying auctions, backed by the `AuctionApi` runtime API.
+
+use std::{fmt::Debug, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_auction::{AuctionInfo, BidRecord};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_auction_rpc_runtime_api::AuctionApi as AuctionRuntimeApi;
+
+/// Most auctions returned by a single `auction_activeAuctions` call
+const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc]
+pub trait AuctionApi<BlockHash, AccountId, AuctionId, Balance, BlockNumber, ClassId, TokenId, CurrencyId> {
+	/// Auction `id`, if it exists
+	#[rpc(name = "auction_getAuction")]
+	fn auction(
+		&self,
+		id: AuctionId,
+		at: Option<BlockHash>,
+	) -> Result<Option<AuctionInfo<AccountId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>>>;
+
+	/// Auctions which have not been concluded yet among the `count` IDs from `start` on
+	#[rpc(name = "auction_activeAuctions")]
+	fn active_auctions(
+		&self,
+		start: AuctionId,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> Result<
+		Vec<(
+			AuctionId,
+			AuctionInfo<AccountId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>,
+		)>,
+	>;
+
+	/// Lowest bid auction `id` accepts next, if it takes bids
+	#[rpc(name = "auction_minimalNextBid")]
+	fn minimal_next_bid(&self, id: AuctionId, at: Option<BlockHash>) -> Result<Option<Balance>>;
+
+	/// Price at which the token of auction `id` can be bought right now, if it is for sale
+	#[rpc(name = "auction_currentPrice")]
+	fn current_price(&self, id: AuctionId, at: Option<BlockHash>) -> Result<Option<Balance>>;
+
+	/// Number of blocks until auction `id` stops taking bids, if it ever does
+	#[rpc(name = "auction_timeRemaining")]
+	fn time_remaining(&self, id: AuctionId, at: Option<BlockHash>) -> Result<Option<BlockNumber>>;
+
+	/// IDs of the auctions of `owner`
+	#[rpc(name = "auction_auctionsByOwner")]
+	fn auctions_by_owner(&self, owner: AccountId, at: Option<BlockHash>) -> Result<Vec<AuctionId>>;
+
+	/// Auction in which the token is listed, if any
+	#[rpc(name = "auction_auctionByToken")]
+	fn auction_by_token(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		at: Option<BlockHash>,
+	) -> Result<Option<AuctionId>>;
+
+	/// Most recent bids of auction `id`, oldest first
+	#[rpc(name = "auction_bidHistory")]
+	fn bid_history(
+		&self,
+		id: AuctionId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<BidRecord<AccountId, Balance, BlockNumber>>>;
+}
+
+/// Implements the `AuctionApi` RPC trait by calling into the runtime
+pub struct Auction<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Auction<C, B> {
+	/// Create new `Auction` with the given reference to the client
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error code of a failed runtime API call
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(err: impl Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query auctions.".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId, AuctionId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>
+	AuctionApi<<Block as BlockT>::Hash, AccountId, AuctionId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>
+	for Auction<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AuctionRuntimeApi<Block, AccountId, AuctionId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>,
+	AccountId: Codec,
+	AuctionId: Codec,
+	Balance: Codec,
+	BlockNumber: Codec,
+	ClassId: Codec,
+	TokenId: Codec,
+	CurrencyId: Codec,
+{
+	fn auction(
+		&self,
+		id: AuctionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AuctionInfo<AccountId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>>> {
+		let at = self.block_id(at);
+		self.client.runtime_api().auction(&at, id).map_err(runtime_error)
+	}
+
+	fn active_auctions(
+		&self,
+		start: AuctionId,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<
+		Vec<(
+			AuctionId,
+			AuctionInfo<AccountId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>,
+		)>,
+	> {
+		let at = self.block_id(at);
+		self.client
+			.runtime_api()
+			.active_auctions(&at, start, count.min(MAX_PAGE_SIZE))
+			.map_err(runtime_error)
+	}
+
+	fn minimal_next_bid(&self, id: AuctionId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Balance>> {
+		let at = self.block_id(at);
+		self.client
+			.runtime_api()
+			.minimal_next_bid(&at, id)
+			.map_err(runtime_error)
+	}
+
+	fn current_price(&self, id: AuctionId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Balance>> {
+		let at = self.block_id(at);
+		self.client.runtime_api().current_price(&at, id).map_err(runtime_error)
+	}
+
+	fn time_remaining(&self, id: AuctionId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<BlockNumber>> {
+		let at = self.block_id(at);
+		self.client.runtime_api().time_remaining(&at, id).map_err(runtime_error)
+	}
+
+	fn auctions_by_owner(&self, owner: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<AuctionId>> {
+		let at = self.block_id(at);
+		self.client
+			.runtime_api()
+			.auctions_by_owner(&at, owner)
+			.map_err(runtime_error)
+	}
+
+	fn auction_by_token(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AuctionId>> {
+		let at = self.block_id(at);
+		self.client
+			.runtime_api()
+			.auction_by_token(&at, class_id, token_id)
+			.map_err(runtime_error)
+	}
+
+	fn bid_history(
+		&self,
+		id: AuctionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<BidRecord<AccountId, Balance, BlockNumber>>> {
+		let at = self.block_id(at);
+		self.client.runtime_api().bid_history(&at, id).map_err(runtime_error)
+	}
+}
+
+impl<C, Block> Auction<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	/// Block to query, the best block unless a hash is given
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
@@ -7,17 +7,302 @@ use pallet_nft::TokenData;
 use sp_std::{boxed::Box, vec};
 
 use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::Hooks;
 use frame_system::RawOrigin;
 
 const SEED: u32 = 0;
 
 fn create_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
 	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, 1_000_000_u32.into());
 	caller
 }
 
 benchmarks! {
 	create_auction {
+		let m in 1 .. T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS);
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let mut tokens = Vec::new();
+		for _ in 0 .. m {
+			let token_data = TokenData { locked:false };
+			let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata.clone(), token_data).unwrap_or_default();
+			tokens.push((class_id, token_id));
+		}
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(1u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::Combinatorial,
+			token_id: tokens[0],
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			bundle: tokens[1..].to_vec(),
+			..Default::default()
+		};
+	}: _(RawOrigin::Signed(caller.clone()), auction_info)
+	verify {
+	}
+
+	bid_value {
+		// Worst case: a candle bid replacing the snapshot of another bidder in the same block, by a
+		// bidder with a snapshot in an earlier block
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let caller3 = create_account::<T>("caller3", 2);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let token = (class_id, token_id);
+		let end = T::MinAuctionDuration::get() + T::BlockNumber::from(20u32);
+		let ending_start = end - T::BlockNumber::from(10u32);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end,
+			owner: caller.clone(),
+			auction_type: AuctionType::Candle,
+			token_id: token,
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			ending_period: Some(T::BlockNumber::from(10u32)),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		frame_system::Module::<T>::set_block_number(1u32.into());
+		AUCTIONS::<T>::bid(caller2.clone(), auction_id, 100_u32.into())?;
+		frame_system::Module::<T>::set_block_number(ending_start);
+		AUCTIONS::<T>::bid(caller3.clone(), auction_id, 1_000_u32.into())?;
+		frame_system::Module::<T>::set_block_number(ending_start + One::one());
+		AUCTIONS::<T>::bid(caller2.clone(), auction_id, 10_000_u32.into())?;
+
+	}: _(RawOrigin::Signed(caller3.clone()), auction_id, 100_000_u32.into())
+	verify {
+	}
+
+	delete_auction {
+		let m in 1 .. T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS);
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let mut tokens = Vec::new();
+		for _ in 0 .. m {
+			let token_data = TokenData { locked:false };
+			let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata.clone(), token_data).unwrap_or_default();
+			tokens.push((class_id, token_id));
+		}
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(20u32),
+			end: T::BlockNumber::from(50u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::Combinatorial,
+			token_id: tokens[0],
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			bundle: tokens[1..].to_vec(),
+			..Default::default()
+		};
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+
+	}: _(RawOrigin::Signed(caller.clone()), auction_id)
+	verify {
+	}
+
+	buy {
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let token = (class_id, token_id);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::Dutch,
+			token_id: token,
+			minimal_bid: Zero::zero(),
+			starting_price: 1_000_000_u32.into(),
+			floor_price: 1_000_u32.into(),
+			price_decay: Some(PriceDecay::Linear(1_000_u32.into())),
+			..Default::default()
+		};
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+
+	}: _(RawOrigin::Signed(caller2.clone()), auction_id)
+	verify {
+	}
+
+	commit_bid {
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let token = (class_id, token_id);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::SealedFirstPrice,
+			token_id: token,
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			reveal_period: Some(T::BlockNumber::from(5u32)),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		frame_system::Module::<T>::set_block_number(1u32.into());
+
+	}: _(RawOrigin::Signed(caller2.clone()), auction_id, T::Hash::default())
+	verify {
+	}
+
+	reveal_bid {
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let token = (class_id, token_id);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::SealedFirstPrice,
+			token_id: token,
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			reveal_period: Some(T::BlockNumber::from(5u32)),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		let value: BalanceOf<T> = 1_000_000_u32.into();
+		let salt = vec![7u8; 32];
+		let commitment = T::Hashing::hash_of(&(value, salt.clone()));
+		frame_system::Module::<T>::set_block_number(1u32.into());
+		AUCTIONS::<T>::commit_bid(RawOrigin::Signed(caller2.clone()).into(), auction_id, commitment)?;
+		frame_system::Module::<T>::set_block_number(16u32.into());
+
+	}: _(RawOrigin::Signed(caller2.clone()), auction_id, value, salt)
+	verify {
+	}
+
+	bid_bundle {
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata.clone(), token_data.clone()).unwrap_or_default();
+		let bundle_token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let token = (class_id, token_id);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::Combinatorial,
+			token_id: token,
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			bundle: vec![(class_id, bundle_token_id)],
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		frame_system::Module::<T>::set_block_number(1u32.into());
+
+	}: _(RawOrigin::Signed(caller2.clone()), auction_id, 0b11, 1_000_000_u32.into())
+	verify {
+	}
+
+	retry_settlement {
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let token = (class_id, token_id);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::English,
+			token_id: token,
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			..Default::default()
+		};
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		AUCTIONS::<T>::bid(caller2.clone(), auction_id, 1_000_000_u32.into()).unwrap_or_default();
+		AUCTIONS::<T>::unschedule_end(auction_id);
+		<FailedSettlements<T>>::insert(auction_id, DispatchError::Other("benchmark"));
+
+	}: _(RawOrigin::Signed(caller2.clone()), auction_id)
+	verify {
+	}
+
+	abort_settlement {
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let token = (class_id, token_id);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::English,
+			token_id: token,
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			..Default::default()
+		};
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		AUCTIONS::<T>::bid(caller2.clone(), auction_id, 1_000_000_u32.into()).unwrap_or_default();
+		AUCTIONS::<T>::unschedule_end(auction_id);
+		<FailedSettlements<T>>::insert(auction_id, DispatchError::Other("benchmark"));
+
+	}: _(RawOrigin::Root, auction_id)
+	verify {
+	}
+
+	update_auction {
 		let caller = create_account::<T>("caller", 0);
 		let class_metadata = "just a token class".as_bytes().to_vec();
 		let class_data = 123;
@@ -34,12 +319,19 @@ benchmarks! {
 			auction_type: AuctionType::English,
 			token_id: token,
 			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			..Default::default()
 		};
-	}: _(RawOrigin::Signed(caller.clone()), auction_info)
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info.clone()).unwrap_or_default();
+		let updated_info = AuctionInfo {
+			end: T::BlockNumber::from(40u32),
+			..auction_info
+		};
+
+	}: _(RawOrigin::Signed(caller.clone()), auction_id, updated_info)
 	verify {
 	}
 
-	bid_value {
+	close_auction {
 		let caller = create_account::<T>("caller", 0);
 		let caller2 = create_account::<T>("caller2", 1);
 		let class_metadata = "just a token class".as_bytes().to_vec();
@@ -57,15 +349,19 @@ benchmarks! {
 			auction_type: AuctionType::English,
 			token_id: token,
 			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			..Default::default()
 		};
 		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		AUCTIONS::<T>::bid(caller2.clone(), auction_id, 1_000_000_u32.into()).unwrap_or_default();
 
-	}: _(RawOrigin::Signed(caller2.clone()), auction_id, 1_000_000_u32.into())
+	}: _(RawOrigin::Signed(caller.clone()), auction_id)
 	verify {
 	}
 
-	delete_auction {
+	cancel_auction {
 		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
 		let class_metadata = "just a token class".as_bytes().to_vec();
 		let class_data = 123;
 		let token_data = TokenData { locked:false };
@@ -75,18 +371,247 @@ benchmarks! {
 		let auction_info = AuctionInfo {
 			name: "Aukce1".as_bytes().to_vec(),
 			last_bid: None,
-			start: T::BlockNumber::from(20u32),
-			end: T::BlockNumber::from(50u32),
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
 			owner: caller.clone(),
 			auction_type: AuctionType::English,
 			token_id: token,
 			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			..Default::default()
 		};
 		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		AUCTIONS::<T>::bid(caller2.clone(), auction_id, 1_000_000_u32.into()).unwrap_or_default();
 
 	}: _(RawOrigin::Signed(caller.clone()), auction_id)
 	verify {
 	}
+
+	on_initialize {
+		let c in 1 .. T::MaxConclusionsPerBlock::get();
+		let caller = create_account::<T>("caller", 0);
+		let caller2 = create_account::<T>("caller2", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		T::Currency::make_free_balance_be(&caller2, 2_000_000_u32.into());
+		for _ in 0 .. c {
+			let token_data = TokenData { locked:false };
+			let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata.clone(), token_data).unwrap_or_default();
+			let auction_info = AuctionInfo {
+				name: "Aukce1".as_bytes().to_vec(),
+				last_bid: None,
+				start: T::BlockNumber::from(0u32),
+				end: T::BlockNumber::from(20u32),
+				owner: caller.clone(),
+				auction_type: AuctionType::English,
+				token_id: (class_id, token_id),
+				minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+				..Default::default()
+			};
+			let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+			AUCTIONS::<T>::bid(caller2.clone(), auction_id, 1_000_u32.into()).unwrap_or_default();
+		}
+		frame_system::Module::<T>::set_block_number(20u32.into());
+
+	}: {
+		AUCTIONS::<T>::on_initialize(20u32.into());
+	}
+	verify {
+	}
+
+	settle_bids {
+		let b in 0 .. T::MaxTopUpBids::get();
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::TopUp,
+			token_id: (class_id, token_id),
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			bid_step: Some(Permill::zero()),
+			min_bid_increment: Some(T::CurrencyBalance::from(T::Balance::from(1_000u32)).into()),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		frame_system::Module::<T>::set_block_number(1u32.into());
+		for i in 0 .. b {
+			let bidder = create_account::<T>("bidder", i);
+			AUCTIONS::<T>::bid(bidder, auction_id, (1_000_u32 * (i + 1)).into())?;
+		}
+
+	}: {
+		AUCTIONS::<T>::settle_auction(auction_id)?;
+	}
+	verify {
+	}
+
+	settle_candle {
+		let s in 1 .. T::MaxCandleEndingPeriod::get().saturated_into::<u32>();
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let end = T::MinAuctionDuration::get() + T::BlockNumber::from(s);
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end,
+			owner: caller.clone(),
+			auction_type: AuctionType::Candle,
+			token_id: (class_id, token_id),
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			ending_period: Some(T::BlockNumber::from(s)),
+			bid_step: Some(Permill::zero()),
+			min_bid_increment: Some(T::CurrencyBalance::from(T::Balance::from(1_000u32)).into()),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		// One snapshot by a different bidder in every block of the ending period
+		for i in 0 .. s {
+			frame_system::Module::<T>::set_block_number(end - T::BlockNumber::from(s - i));
+			let bidder = create_account::<T>("bidder", i);
+			AUCTIONS::<T>::bid(bidder, auction_id, (1_000_u32 * (i + 1)).into())?;
+		}
+
+	}: {
+		AUCTIONS::<T>::settle_auction(auction_id)?;
+	}
+	verify {
+	}
+
+	settle_bundle {
+		let m in 1 .. T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS);
+		let b in 1 .. T::MaxBundleBids::get();
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let mut tokens = Vec::new();
+		for _ in 0 .. m {
+			let token_data = TokenData { locked:false };
+			let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata.clone(), token_data).unwrap_or_default();
+			tokens.push((class_id, token_id));
+		}
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(0u32),
+			end: T::BlockNumber::from(20u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::Combinatorial,
+			token_id: tokens[0],
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			bundle: tokens[1..].to_vec(),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		frame_system::Module::<T>::set_block_number(1u32.into());
+		let bundles = (1u32 << m) - 1;
+		for i in 0 .. b {
+			let bidder = create_account::<T>("bidder", i);
+			AUCTIONS::<T>::place_bundle_bid(bidder, auction_id, i % bundles + 1, (1_000_u32 * (i + 1)).into())?;
+		}
+
+	}: {
+		AUCTIONS::<T>::settle_auction(auction_id)?;
+	}
+	verify {
+	}
+
+	pay_royalties {
+		let r in 1 .. T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS);
+		let caller = create_account::<T>("caller", 0);
+		let creator = create_account::<T>("creator", 1);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let mut tokens = Vec::new();
+		// Every token of its own class, each paying a royalty to the creator
+		for _ in 0 .. r {
+			let token_data = TokenData { locked:false };
+			let class_id = orml_nft::Module::<T>::create_class(&creator, class_metadata.clone(), class_data).unwrap_or_default();
+			pallet_nft::ClassRoyalties::<T>::insert(class_id, pallet_nft::Royalty {
+				beneficiary: creator.clone(),
+				rate: Permill::from_percent(10),
+			});
+			let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata.clone(), token_data).unwrap_or_default();
+			tokens.push((class_id, token_id));
+		}
+
+	}: {
+		AUCTIONS::<T>::pay_royalties(Default::default(), &caller, &tokens, 100_000_u32.into(), None)?;
+	}
+	verify {
+	}
+
+	commit_reserve {
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(10u32),
+			end: T::BlockNumber::from(40u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::English,
+			token_id: (class_id, token_id),
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+
+	}: _(RawOrigin::Signed(caller.clone()), auction_id, T::Hash::default())
+	verify {
+	}
+
+	reveal_reserve {
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let token_data = TokenData { locked:false };
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata.clone(), class_data).unwrap_or_default();
+		let token_id = orml_nft::Module::<T>::mint(&caller, class_id, class_metadata, token_data).unwrap_or_default();
+		let auction_info = AuctionInfo {
+			name: "Aukce1".as_bytes().to_vec(),
+			last_bid: None,
+			start: T::BlockNumber::from(10u32),
+			end: T::BlockNumber::from(40u32),
+			owner: caller.clone(),
+			auction_type: AuctionType::English,
+			token_id: (class_id, token_id),
+			minimal_bid: T::CurrencyBalance::from(T::Balance::from(50u32)).into(),
+			..Default::default()
+		};
+		frame_system::Module::<T>::set_block_number(0u32.into());
+		let auction_id = AUCTIONS::<T>::new_auction(auction_info).unwrap_or_default();
+		let reserve: BalanceOf<T> = 1_000_u32.into();
+		let salt = vec![7u8; 32];
+		let commitment = T::Hashing::hash_of(&(reserve, salt.clone()));
+		AUCTIONS::<T>::commit_reserve(RawOrigin::Signed(caller.clone()).into(), auction_id, commitment)?;
+		frame_system::Module::<T>::set_block_number(20u32.into());
+
+	}: _(RawOrigin::Signed(caller.clone()), auction_id, reserve, salt)
+	verify {
+	}
 }
 
 #[cfg(test)]
@@ -102,6 +627,22 @@ mod tests {
 			assert_ok!(test_benchmark_create_auction::<Test>());
 			assert_ok!(test_benchmark_bid_value::<Test>());
 			assert_ok!(test_benchmark_delete_auction::<Test>());
+			assert_ok!(test_benchmark_buy::<Test>());
+			assert_ok!(test_benchmark_commit_bid::<Test>());
+			assert_ok!(test_benchmark_reveal_bid::<Test>());
+			assert_ok!(test_benchmark_bid_bundle::<Test>());
+			assert_ok!(test_benchmark_retry_settlement::<Test>());
+			assert_ok!(test_benchmark_abort_settlement::<Test>());
+			assert_ok!(test_benchmark_on_initialize::<Test>());
+			assert_ok!(test_benchmark_settle_bids::<Test>());
+			assert_ok!(test_benchmark_settle_candle::<Test>());
+			assert_ok!(test_benchmark_settle_bundle::<Test>());
+			assert_ok!(test_benchmark_pay_royalties::<Test>());
+			assert_ok!(test_benchmark_update_auction::<Test>());
+			assert_ok!(test_benchmark_close_auction::<Test>());
+			assert_ok!(test_benchmark_cancel_auction::<Test>());
+			assert_ok!(test_benchmark_commit_reserve::<Test>());
+			assert_ok!(test_benchmark_reveal_reserve::<Test>());
 		});
 	}
 }
@@ -2,22 +2,28 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 // Used for encoding/decoding into scale
-use frame_support::traits::ExistenceRequirement;
+use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
 	ensure,
-	traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons},
+	traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Get, OnUnbalanced, Randomness, ReservableCurrency,
+		WithdrawReasons,
+	},
+	transactional,
+	weights::Weight,
 	Parameter,
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_root, ensure_signed};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
 use sp_runtime::{
 	traits::{
-		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, StaticLookup,
-		Zero,
+		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, Saturating,
+		StaticLookup, Zero,
 	},
-	Permill,
+	Permill, SaturatedConversion,
 };
-use sp_std::result;
+use sp_std::{iter, result, vec, vec::Vec};
 pub use traits::*;
 use weights::WeightInfo;
 
@@ -32,25 +38,29 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-/// Identifier for the currency lock on accounts
-const AUCTION_LOCK_ID: LockIdentifier = *b"_auction";
-/// Set in percent how much next bid has to be raised
-const BID_STEP_PERC: u32 = 10;
-/// Increase endtime to avoid sniping
-const BID_ADD_BLOCKS: u32 = 10;
-/// Minimal auction duration
-const MIN_AUCTION_DUR: u32 = 10;
+/// Upper bound of items in a combinatorial auction, each of them is a bit of a `u32` mask
+const MAX_BUNDLE_ITEMS: u32 = 16;
+/// Subject used when drawing the retroactive end of a candle auction
+const CANDLE_RANDOM_SUBJECT: &[u8] = b"auction/candle";
 
 /// Define type aliases for better readability
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 pub type NftClassIdOf<T> = pallet_nft::ClassIdOf<T>;
 pub type NftTokenIdOf<T> = pallet_nft::TokenIdOf<T>;
+pub type BundleBidOf<T> = BundleBid<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+pub type BidRecordOf<T> =
+	BidRecord<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+pub type SealedBidOf<T> =
+	SealedBid<<T as frame_system::Config>::Hash, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 pub type AuctionInfoOf<T> = AuctionInfo<
 	<T as frame_system::Config>::AccountId,
 	BalanceOf<T>,
 	<T as frame_system::Config>::BlockNumber,
 	NftClassIdOf<T>,
 	NftTokenIdOf<T>,
+	<T as Config>::CurrencyId,
 >;
 
 pub use pallet::*;
@@ -81,8 +91,105 @@ pub mod pallet {
 			+ Bounded
 			+ CheckedAdd;
 
-		/// Single type currency (TODO multiple currencies)
-		type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+		/// Native currency, used for the deposits and for the auctions which don't set a currency
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Identifier of the other currencies an auction can be held in
+		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord + Default;
+
+		/// Other currencies an auction can be held in, sharing the balance type of the native one
+		type MultiCurrency: MultiReservableCurrency<
+			Self::AccountId,
+			CurrencyId = Self::CurrencyId,
+			Balance = BalanceOf<Self>,
+		>;
+
+		/// Source of randomness used to close candle auctions
+		type Randomness: Randomness<Self::Hash>;
+
+		/// Deposit reserved with every sealed bid, slashed if the bid is never revealed
+		#[pallet::constant]
+		type SealedBidDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of tokens in a combinatorial auction
+		#[pallet::constant]
+		type MaxBundleItems: Get<u32>;
+
+		/// Maximum number of bids in a combinatorial auction
+		#[pallet::constant]
+		type MaxBundleBids: Get<u32>;
+
+		/// Maximum number of bids in a top-up auction, each of them is kept until the settlement
+		#[pallet::constant]
+		type MaxTopUpBids: Get<u32>;
+
+		/// Maximum number of bidders committed to a sealed auction
+		#[pallet::constant]
+		type MaxSealedBids: Get<u32>;
+
+		/// Maximum number of auctions concluded in a single block, the rest waits for the next blocks
+		#[pallet::constant]
+		type MaxConclusionsPerBlock: Get<u32>;
+
+		/// Longest ending period of a candle auction, which bounds the snapshots settled with it
+		#[pallet::constant]
+		type MaxCandleEndingPeriod: Get<Self::BlockNumber>;
+
+		/// Deposit reserved from the owner of every auction, paid to the leading bidder if the owner
+		/// cancels the auction
+		#[pallet::constant]
+		type CancellationPenalty: Get<BalanceOf<Self>>;
+
+		/// How much the next bid has to be raised, unless the auction sets its own step
+		#[pallet::constant]
+		type BidStep: Get<Permill>;
+
+		/// Lower bound of the bid step an auction can set, unless it requires a minimal increment
+		#[pallet::constant]
+		type MinBidStep: Get<Permill>;
+
+		/// Upper bound of the bid step an auction can set
+		#[pallet::constant]
+		type MaxBidStep: Get<Permill>;
+
+		/// A bid this many blocks before the end postpones it to avoid sniping, unless the auction
+		/// sets its own number of blocks
+		#[pallet::constant]
+		type AntiSnipeBlocks: Get<Self::BlockNumber>;
+
+		/// Upper bound of the anti-sniping blocks an auction can set
+		#[pallet::constant]
+		type MaxAntiSnipeBlocks: Get<Self::BlockNumber>;
+
+		/// Minimal auction duration
+		#[pallet::constant]
+		type MinAuctionDuration: Get<Self::BlockNumber>;
+
+		/// Maximum number of bids kept in the history of an auction, older ones are dropped
+		#[pallet::constant]
+		type MaxBidHistory: Get<u32>;
+
+		/// Storage deposit reserved from the owner of every auction until it is removed
+		#[pallet::constant]
+		type AuctionDepositBase: Get<BalanceOf<Self>>;
+
+		/// Additional storage deposit per byte of the encoded auction
+		#[pallet::constant]
+		type AuctionDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Maximum length of the name of an auction
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+
+		/// Share of every sale charged to the owner at settlement
+		#[pallet::constant]
+		type Commission: Get<Permill>;
+
+		/// Receives the commission charged in the native currency
+		type CommissionDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Account receiving the commission charged in the currencies of `MultiCurrency`
+		type TokenCommissionAccount: Get<Self::AccountId>;
 
 		/// Weights
 		type WeightInfo: WeightInfo;
@@ -109,11 +216,130 @@ pub mod pallet {
 	pub type AuctionEndTime<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, T::AuctionId, (), OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn auction_end_by_id)]
+	/// Block at which each auction is indexed in `AuctionEndTime`
+	pub type AuctionEndById<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, T::BlockNumber, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn auction_owner_by_id)]
 	/// Auction owner by ID
 	pub type AuctionOwnerById<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, T::AccountId, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_by_owner)]
+	/// Auctions of each owner
+	pub type AuctionsByOwner<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::AuctionId, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auction_by_token)]
+	/// Auction in which a token is listed, by class and token ID
+	pub type AuctionByToken<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, NftClassIdOf<T>, Twox64Concat, NftTokenIdOf<T>, T::AuctionId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_by_bidder)]
+	/// Auctions in which an account has a bid backed by reserved funds
+	pub type AuctionsByBidder<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::AuctionId, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reserved_bids)]
+	/// Funds reserved by an account to back its bids, per auction
+	pub type ReservedBids<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::AuctionId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candle_snapshots)]
+	/// Leading bid of a candle auction at each block of its ending period in which it changed
+	pub type CandleSnapshots<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AuctionId,
+		Twox64Concat,
+		T::BlockNumber,
+		(T::AccountId, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candle_bidders)]
+	/// Block of the latest snapshot of each bidder still kept in a snapshot of a candle auction, with
+	/// its highest bid in a snapshot of an earlier block
+	pub type CandleBidders<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AuctionId,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::BlockNumber, Option<BalanceOf<T>>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn top_up_bids)]
+	/// Every bid placed in a top-up auction, from the lowest to the highest
+	pub type TopUpBids<T: Config> =
+		StorageMap<_, Twox64Concat, T::AuctionId, Vec<(T::AccountId, BalanceOf<T>)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_bids)]
+	/// Bids committed to sealed auctions
+	pub type SealedBids<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AuctionId, Blake2_128Concat, T::AccountId, SealedBidOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_bid_count)]
+	/// Number of bids committed to each sealed auction
+	pub type SealedBidCount<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bundle_bids)]
+	/// Bids placed on bundles of combinatorial auctions
+	pub type BundleBids<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, Vec<BundleBidOf<T>>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_record)]
+	/// Most recent accepted bids of each auction, by their slot in a ring of `MaxBidHistory` slots
+	pub type BidHistory<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AuctionId, Twox64Concat, u32, BidRecordOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_history_bounds)]
+	/// Slot of the next bid in the history of each auction and the number of bids it holds
+	pub type BidHistoryBounds<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, (u32, u32), ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_commitments)]
+	/// Hash of the hidden reserve price of each auction and its salt, until the owner reveals it
+	pub type ReserveCommitments<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, T::Hash, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn failed_settlements)]
+	/// Ended auctions whose settlement failed, with the reason, waiting to be retried
+	pub type FailedSettlements<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, DispatchError, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn queued_conclusion)]
+	/// Ended auctions which did not fit into the conclusion limit of their block, by position in the queue
+	pub type ConclusionQueue<T: Config> = StorageMap<_, Twox64Concat, u32, T::AuctionId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn conclusion_queue_bounds)]
+	/// Position of the oldest queued conclusion and the position after the newest one
+	pub type ConclusionQueueBounds<T: Config> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn seller_deposits)]
+	/// Cancellation deposit reserved by the owner of each auction
+	pub type SellerDeposits<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn storage_deposits)]
+	/// Storage deposit reserved by the owner of each auction
+	pub type StorageDeposits<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, BalanceOf<T>, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -121,10 +347,49 @@ pub mod pallet {
 		AuctionCreated(T::AccountId, T::AuctionId),
 		/// A bid is placed
 		Bid(T::AuctionId, T::AccountId, BalanceOf<T>),
-		/// Auction ended
-		AuctionConcluded(T::AuctionId),
+		/// Auction ended, the owner kept the first amount and the commission took the second
+		AuctionConcluded(T::AuctionId, BalanceOf<T>, BalanceOf<T>),
+		/// Royalty of a sold token was paid to the beneficiary of its class
+		RoyaltyPaid(T::AuctionId, T::AccountId, BalanceOf<T>),
+		/// Late bid or the owner postponed the end of an auction to the given block
+		AuctionExtended(T::AuctionId, T::BlockNumber),
+		/// Auction ended without a winner, or below its reserve price, and the token stays with the owner
+		AuctionConcludedWithoutSale(T::AuctionId),
+		/// Settlement of an ended auction failed and was rolled back, it can be retried
+		SettlementFailed(T::AuctionId, DispatchError),
+		/// Failed settlement was given up, every bid was refunded and the token stays with the owner
+		SettlementAborted(T::AuctionId),
+		/// Candle auction was retroactively closed at the given block
+		CandleAuctionClosed(T::AuctionId, T::BlockNumber),
 		/// Auction removed
 		AuctionRemoved(T::AuctionId),
+		/// Terms of an auction were changed by its owner
+		AuctionUpdated(T::AuctionId),
+		/// Running auction was cancelled by its owner, the leading bidder received the given penalty
+		AuctionCancelled(T::AuctionId, BalanceOf<T>),
+		/// Token was bought outright, the owner kept the first amount and the commission took the second
+		AuctionSold(T::AuctionId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// English auction was ended by paying its buy now price, the owner kept the first amount and
+		/// the commission took the second
+		AuctionBoughtNow(T::AuctionId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Outbid participant of a top-up auction paid its fee
+		TopUpFeePaid(T::AuctionId, T::AccountId, BalanceOf<T>),
+		/// Sealed bid committed
+		BidCommitted(T::AuctionId, T::AccountId),
+		/// Sealed bid revealed
+		BidRevealed(T::AuctionId, T::AccountId, BalanceOf<T>),
+		/// Sealed auction won by the account for the given price
+		SealedAuctionWon(T::AuctionId, T::AccountId, BalanceOf<T>),
+		/// Deposit of a sealed bid which was never revealed was slashed
+		SealedBidDepositSlashed(T::AuctionId, T::AccountId, BalanceOf<T>),
+		/// A bid is placed on a bundle of items
+		BundleBid(T::AuctionId, T::AccountId, u32, BalanceOf<T>),
+		/// Bundle of items was won for the given amount
+		BundleWon(T::AuctionId, T::AccountId, u32, BalanceOf<T>),
+		/// Owner committed to a hidden reserve price
+		ReserveCommitted(T::AuctionId),
+		/// Hidden reserve price was revealed and applies to the auction
+		ReserveRevealed(T::AuctionId, BalanceOf<T>),
 	}
 
 	#[pallet::error]
@@ -145,13 +410,37 @@ pub mod pallet {
 		TimeUnderflow,
 		TokenLocked,
 		EmptyAuctionName,
+		InsufficientReservedFunds,
+		InvalidEndingPeriod,
+		InvalidPriceConfiguration,
+		NotForSale,
+		InvalidRevealPeriod,
+		NotInCommitPhase,
+		NotInRevealPhase,
+		BidNotCommitted,
+		BidAlreadyRevealed,
+		InvalidReveal,
+		InvalidBundle,
+		TooManyBundleBids,
+		SettlementNotFailed,
+		NotAuctionOwner,
+		InvalidAuctionUpdate,
+		InvalidInactivityWindow,
+		InvalidBidRules,
+		CannotCloseAuction,
+		NoBidToAccept,
+		AuctionNameTooLong,
+		TooManyTopUpBids,
+		TooManySealedBids,
+		ReserveNotCommitted,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(<T as Config>::WeightInfo::create_auction())]
+		#[pallet::weight(<T as Config>::WeightInfo::create_auction(auction_info.bundle.len() as u32 + 1))]
 		pub fn create_auction(origin: OriginFor<T>, auction_info: AuctionInfoOf<T>) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
+			ensure!(auction_info.owner == sender, Error::<T>::NotAuctionOwner);
 			let new_auction_id = Self::new_auction(auction_info)?;
 			Self::deposit_event(Event::AuctionCreated(sender, new_auction_id));
 			Ok(().into())
@@ -166,44 +455,1227 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(<T as Config>::WeightInfo::delete_auction())]
+		#[pallet::weight(<T as Config>::WeightInfo::delete_auction(T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS)))]
 		pub fn delete_auction(origin: OriginFor<T>, id: T::AuctionId) -> DispatchResultWithPostInfo {
-			let _sender = ensure_signed(origin)?;
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(auction.owner == sender, Error::<T>::NotAuctionOwner);
+
+			Self::remove_auction(id)?;
+			Self::deposit_event(Event::AuctionRemoved(id));
+			let items = Self::auction_tokens(&auction).count() as u32;
+			Ok(Some(<T as Config>::WeightInfo::delete_auction(items)).into())
+		}
+
+		/// Change the terms of an auction of the sender, within the limits of `Auction::update_auction`
+		#[pallet::weight(<T as Config>::WeightInfo::update_auction())]
+		pub fn update_auction(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			auction_info: AuctionInfoOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(auction.owner == sender, Error::<T>::NotAuctionOwner);
+
+			<Self as Auction<_, _, _, _>>::update_auction(id, auction_info)?;
+			Self::deposit_event(Event::AuctionUpdated(id));
+			Ok(().into())
+		}
+
+		/// Accept the highest bid of a running English or top-up auction of the sender and settle it
+		/// right away, even below the reserve price
+		#[pallet::weight(
+			<T as Config>::WeightInfo::close_auction().saturating_add(Pallet::<T>::max_settlement_weight())
+		)]
+		#[transactional]
+		pub fn close_auction(origin: OriginFor<T>, id: T::AuctionId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let mut auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(auction.owner == sender, Error::<T>::NotAuctionOwner);
+			ensure!(
+				matches!(auction.auction_type, AuctionType::English | AuctionType::TopUp),
+				Error::<T>::CannotCloseAuction
+			);
+			ensure!(auction.last_bid.is_some(), Error::<T>::NoBidToAccept);
+			// Ended auctions waiting in the queue or for a retry settle on their original terms
+			let block_number = frame_system::Module::<T>::block_number();
+			ensure!(
+				block_number < Self::ends_at(id, &auction),
+				Error::<T>::AuctionAlreadyConcluded
+			);
+
+			let weight = Self::settlement_weight(id, &auction);
+			Self::unschedule_end(id);
+			auction.reserve_price = None;
+			<Auctions<T>>::insert(id, auction);
+			Self::settle_auction(id)?;
+			<FailedSettlements<T>>::remove(id);
+			Ok(Some(<T as Config>::WeightInfo::close_auction().saturating_add(weight)).into())
+		}
+
+		/// Cancel a running auction of the sender. Every bid is refunded and the leading bidder
+		/// receives the cancellation deposit of the sender.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::cancel_auction().saturating_add(Pallet::<T>::max_settlement_weight())
+		)]
+		#[transactional]
+		pub fn cancel_auction(origin: OriginFor<T>, id: T::AuctionId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(auction.owner == sender, Error::<T>::NotAuctionOwner);
+			let block_number = frame_system::Module::<T>::block_number();
+			ensure!(Self::has_started(&auction, block_number), Error::<T>::AuctionNotStarted);
+			ensure!(
+				block_number < Self::ends_at(id, &auction) || Self::never_expires(&auction),
+				Error::<T>::AuctionAlreadyConcluded
+			);
+
+			let weight = Self::settlement_weight(id, &auction);
+			Self::toggle_token_locks(&auction)?;
+			Self::remove_auction_entries(id, &auction);
+			Self::release_all_bid_funds(id, &auction);
+			let deposit = <SellerDeposits<T>>::take(id);
+			let penalty = match &auction.last_bid {
+				Some((leader, _)) => {
+					let unpaid = T::Currency::repatriate_reserved(&sender, leader, deposit, BalanceStatus::Free)?;
+					deposit.saturating_sub(unpaid)
+				}
+				None => {
+					T::Currency::unreserve(&sender, deposit);
+					Zero::zero()
+				}
+			};
+			Self::deposit_event(Event::AuctionCancelled(id, penalty));
+			Ok(Some(<T as Config>::WeightInfo::cancel_auction().saturating_add(weight)).into())
+		}
+
+		/// Remove any auction which can still be removed, regardless of its owner
+		#[pallet::weight(<T as Config>::WeightInfo::delete_auction(T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS)))]
+		pub fn force_delete_auction(origin: OriginFor<T>, id: T::AuctionId) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
 
 			Self::remove_auction(id)?;
 			Self::deposit_event(Event::AuctionRemoved(id));
+			let items = Self::auction_tokens(&auction).count() as u32;
+			Ok(Some(<T as Config>::WeightInfo::delete_auction(items)).into())
+		}
+
+		/// Buy the token of a Dutch auction or a fixed swap listing at its current price, or end an
+		/// English auction right away by paying its buy now price
+		#[pallet::weight(
+			<T as Config>::WeightInfo::buy().saturating_add(T::DbWeight::get().writes(T::MaxBidHistory::get() as Weight))
+		)]
+		#[transactional]
+		pub fn buy(origin: OriginFor<T>, id: T::AuctionId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+
+			let history_weight = Self::bid_history_weight(id);
+			let (proceeds, commission) = Self::purchase(sender.clone(), id, &auction)?;
+			if auction.auction_type == AuctionType::English {
+				Self::deposit_event(Event::AuctionBoughtNow(id, sender, proceeds, commission));
+			} else {
+				Self::deposit_event(Event::AuctionSold(id, sender, proceeds, commission));
+			}
+			Ok(Some(<T as Config>::WeightInfo::buy().saturating_add(history_weight)).into())
+		}
+
+		/// Retry the settlement of an ended auction which previously failed
+		#[pallet::weight(
+			<T as Config>::WeightInfo::retry_settlement().saturating_add(Pallet::<T>::max_settlement_weight())
+		)]
+		pub fn retry_settlement(origin: OriginFor<T>, id: T::AuctionId) -> DispatchResultWithPostInfo {
+			let _sender = ensure_signed(origin)?;
+
+			ensure!(
+				<FailedSettlements<T>>::contains_key(id),
+				Error::<T>::SettlementNotFailed
+			);
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			let weight = Self::settlement_weight(id, &auction);
+			Self::settle_auction(id)?;
+			<FailedSettlements<T>>::remove(id);
+			Ok(Some(<T as Config>::WeightInfo::retry_settlement().saturating_add(weight)).into())
+		}
+
+		/// Give up the failed settlement of an ended auction which can never succeed. Every bidder
+		/// is refunded and the tokens are unlocked for the owner.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::abort_settlement().saturating_add(Pallet::<T>::max_settlement_weight())
+		)]
+		#[transactional]
+		pub fn abort_settlement(origin: OriginFor<T>, id: T::AuctionId) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(
+				<FailedSettlements<T>>::contains_key(id),
+				Error::<T>::SettlementNotFailed
+			);
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			let weight = Self::settlement_weight(id, &auction);
+			Self::toggle_token_locks(&auction)?;
+			Self::remove_auction_entries(id, &auction);
+			Self::release_all_bid_funds(id, &auction);
+			Self::release_seller_deposit(id, &auction.owner);
+			<FailedSettlements<T>>::remove(id);
+			Self::deposit_event(Event::SettlementAborted(id));
+			Ok(Some(<T as Config>::WeightInfo::abort_settlement().saturating_add(weight)).into())
+		}
+
+		/// Commit to a bid in a sealed auction with the hash of the encoded `(amount, salt)` pair
+		#[pallet::weight(<T as Config>::WeightInfo::commit_bid())]
+		#[transactional]
+		pub fn commit_bid(origin: OriginFor<T>, id: T::AuctionId, commitment: T::Hash) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::commit_sealed_bid(sender.clone(), id, commitment)?;
+			Self::deposit_event(Event::BidCommitted(id, sender));
+			Ok(().into())
+		}
+
+		/// Bid on a bundle of items of a combinatorial auction, `items` is a bitmask of the items
+		#[pallet::weight(<T as Config>::WeightInfo::bid_bundle())]
+		#[transactional]
+		pub fn bid_bundle(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			items: u32,
+			value: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::place_bundle_bid(sender.clone(), id, items, value)?;
+			Self::deposit_event(Event::BundleBid(id, sender, items, value));
+			Ok(().into())
+		}
+
+		/// Reveal a previously committed bid in a sealed auction
+		#[pallet::weight(<T as Config>::WeightInfo::reveal_bid())]
+		#[transactional]
+		pub fn reveal_bid(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			value: BalanceOf<T>,
+			salt: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::reveal_sealed_bid(sender.clone(), id, value, salt)?;
+			Self::deposit_event(Event::BidRevealed(id, sender, value));
+			Ok(().into())
+		}
+
+		/// Commit to a hidden reserve price of an auction of the sender before it starts, with the
+		/// hash of the encoded `(reserve, salt)` pair. Without a reveal before the end no reserve applies.
+		#[pallet::weight(<T as Config>::WeightInfo::commit_reserve())]
+		pub fn commit_reserve(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			commitment: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(auction.owner == sender, Error::<T>::NotAuctionOwner);
+			let block_number = frame_system::Module::<T>::block_number();
+			ensure!(
+				!Self::has_started(&auction, block_number),
+				Error::<T>::AuctionAlreadyStarted
+			);
+			ensure!(
+				Self::accepts_reserve_price(&auction) && auction.reserve_price.is_none(),
+				Error::<T>::InvalidPriceConfiguration
+			);
+
+			// Committing again replaces the previous commitment
+			<ReserveCommitments<T>>::insert(id, commitment);
+			Self::deposit_event(Event::ReserveCommitted(id));
+			Ok(().into())
+		}
+
+		/// Reveal the hidden reserve price of an auction of the sender, it applies from now on
+		#[pallet::weight(<T as Config>::WeightInfo::reveal_reserve())]
+		#[transactional]
+		pub fn reveal_reserve(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			reserve: BalanceOf<T>,
+			salt: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let mut auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(auction.owner == sender, Error::<T>::NotAuctionOwner);
+			let commitment = Self::reserve_commitments(id).ok_or(Error::<T>::ReserveNotCommitted)?;
+			ensure!(
+				T::Hashing::hash_of(&(reserve, salt)) == commitment,
+				Error::<T>::InvalidReveal
+			);
+			// Ended auctions waiting in the queue or for a retry settle on their original terms
+			let block_number = frame_system::Module::<T>::block_number();
+			ensure!(
+				block_number < Self::ends_at(id, &auction),
+				Error::<T>::AuctionAlreadyConcluded
+			);
+			ensure!(auction.reserve_price.is_none(), Error::<T>::InvalidPriceConfiguration);
+
+			auction.reserve_price = Some(reserve);
+			Self::check_auction_terms(&auction)?;
+			Self::adjust_storage_deposit(id, &auction)?;
+			<Auctions<T>>::insert(id, auction);
+			<ReserveCommitments<T>>::remove(id);
+			Self::deposit_event(Event::ReserveRevealed(id, reserve));
 			Ok(().into())
 		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		fn on_finalize(now: T::BlockNumber) {
-			Self::conclude_auction(now);
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::conclude_auction(now)
+		}
+
+		fn integrity_test() {
+			// The owner has to be able to pay both the commission and the royalties of a sale
+			let charged = T::Commission::get().deconstruct() as u64 + T::MaxRoyalty::get().deconstruct() as u64;
+			assert!(charged <= Permill::one().deconstruct() as u64);
 		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	fn conclude_auction(now: T::BlockNumber) {
+	/// Current price at which the token of auction `id` can be bought, if it is for sale
+	pub fn current_price(id: T::AuctionId) -> Option<BalanceOf<T>> {
+		let auction = Self::auctions(id)?;
+		Self::price_at(&auction, frame_system::Module::<T>::block_number())
+	}
+
+	/// Most recent accepted bids of auction `id`, oldest first
+	pub fn bid_history(id: T::AuctionId) -> Vec<BidRecordOf<T>> {
+		let max_history = T::MaxBidHistory::get();
+		let (next, len) = Self::bid_history_bounds(id);
+		let len = len.min(max_history);
+		// Once the ring is full the next slot holds the oldest bid
+		let oldest = (next + max_history - len) % max_history.max(1);
+		(0..len)
+			.filter_map(|i| Self::bid_record(id, (oldest + i) % max_history))
+			.collect()
+	}
+
+	/// Auctions which have not been concluded yet among the `count` IDs from `start` on
+	pub fn active_auctions(start: T::AuctionId, count: u32) -> Vec<(T::AuctionId, AuctionInfoOf<T>)> {
+		// Concluded auctions count towards the page as well, so that a page never scans more IDs
+		let end = start.saturating_add(count.into()).min(Self::auctions_index());
+		let mut auctions = Vec::new();
+		let mut id = start;
+		while id < end {
+			if let Some(auction) = Self::auctions(id) {
+				auctions.push((id, auction));
+			}
+			id += One::one();
+		}
+		auctions
+	}
+
+	/// Lowest bid auction `id` accepts next, if it takes bids
+	pub fn minimal_next_bid(id: T::AuctionId) -> Option<BalanceOf<T>> {
+		let auction = Self::auctions(id)?;
+		match auction.auction_type {
+			AuctionType::Dutch | AuctionType::FixedSwap => None,
+			_ => Some(auction.minimal_bid),
+		}
+	}
+
+	/// Number of blocks until auction `id` stops taking bids, if it ever does
+	pub fn time_remaining(id: T::AuctionId) -> Option<T::BlockNumber> {
+		let auction = Self::auctions(id)?;
+		if Self::never_expires(&auction) {
+			return None;
+		}
+		let block_number = frame_system::Module::<T>::block_number();
+		Some(Self::ends_at(id, &auction).saturating_sub(block_number))
+	}
+
+	/// IDs of the auctions of `owner`
+	pub fn owner_auctions(owner: &T::AccountId) -> Vec<T::AuctionId> {
+		<AuctionsByOwner<T>>::iter_prefix(owner).map(|(id, _)| id).collect()
+	}
+
+	/// Price at which the token can be bought outright at block `now`
+	pub fn price_at(auction: &AuctionInfoOf<T>, now: T::BlockNumber) -> Option<BalanceOf<T>> {
+		match auction.auction_type {
+			AuctionType::Dutch => Self::dutch_price(auction, now),
+			AuctionType::FixedSwap => Some(auction.starting_price),
+			AuctionType::English => auction
+				.buy_now_price
+				.filter(|price| auction.last_bid.as_ref().map_or(true, |(_, bid)| bid < price)),
+			_ => None,
+		}
+	}
+
+	/// An auction runs from the block after its start, the first one to accept bids
+	fn has_started(auction: &AuctionInfoOf<T>, now: T::BlockNumber) -> bool {
+		now > auction.start
+	}
+
+	/// Whether the winner of the auction can be held to a reserve price
+	fn accepts_reserve_price(auction: &AuctionInfoOf<T>) -> bool {
+		matches!(
+			auction.auction_type,
+			AuctionType::English | AuctionType::Candle | AuctionType::Vickrey
+		)
+	}
+
+	/// Fixed swaps without an end are listed until bought or removed
+	fn never_expires(auction: &AuctionInfoOf<T>) -> bool {
+		auction.auction_type == AuctionType::FixedSwap && auction.end.is_zero()
+	}
+
+	/// Price of a Dutch auction at block `now`, never lower than its floor price
+	pub fn dutch_price(auction: &AuctionInfoOf<T>, now: T::BlockNumber) -> Option<BalanceOf<T>> {
+		if auction.auction_type != AuctionType::Dutch {
+			return None;
+		}
+		let (amount, blocks) = auction.price_decay?.rate();
+		let steps: u32 = (now.saturating_sub(auction.start) / blocks.max(One::one())).saturated_into();
+		let price = auction
+			.starting_price
+			.saturating_sub(amount.saturating_mul(steps.into()));
+		Some(price.max(auction.floor_price))
+	}
+
+	/// Block at which the price of a Dutch auction reaches its floor
+	fn dutch_floor_block(info: &AuctionInfoOf<T>) -> Option<T::BlockNumber> {
+		if info.auction_type != AuctionType::Dutch {
+			return None;
+		}
+		let (amount, blocks) = info.price_decay?.rate();
+		if amount.is_zero() {
+			return None;
+		}
+		// Number of price drops needed to get from the starting price to the floor, rounded up
+		let range = info.starting_price.saturating_sub(info.floor_price);
+		let steps: u32 = (range.saturating_add(amount - One::one()) / amount).saturated_into();
+		Some(info.start.saturating_add(blocks.saturating_mul(steps.into())))
+	}
+
+	/// Transfers the current price from `buyer` to the owner and the token to `buyer`
+	fn purchase(
+		buyer: T::AccountId,
+		id: T::AuctionId,
+		auction: &AuctionInfoOf<T>,
+	) -> result::Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let block_number = frame_system::Module::<T>::block_number();
+		ensure!(buyer != auction.owner, Error::<T>::BidOnOwnAuction);
+		ensure!(Self::has_started(auction, block_number), Error::<T>::AuctionNotStarted);
+		ensure!(
+			block_number < Self::ends_at(id, auction) || Self::never_expires(auction),
+			Error::<T>::AuctionAlreadyConcluded
+		);
+		let price = Self::price_at(auction, block_number).ok_or(Error::<T>::NotForSale)?;
+
+		// The leader of an English auction gets its funds back
+		if let Some((leader, _)) = &auction.last_bid {
+			Self::release_bid_funds(leader, id, auction.currency_id);
+		}
+		Self::transfer_in(
+			auction.currency_id,
+			&buyer,
+			&auction.owner,
+			price,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		pallet_nft::Module::<T>::toggle_lock(&auction.owner, auction.token_id)?;
+		let dest = T::Lookup::unlookup(buyer);
+		let source = T::Origin::from(frame_system::RawOrigin::Signed(auction.owner.clone()));
+		pallet_nft::Module::<T>::transfer(source, dest, auction.token_id).map_err(|e| e.error)?;
+		let sale = Self::charge_seller(id, &auction.owner, &[auction.token_id], price, auction.currency_id)?;
+
+		Self::remove_auction_entries(id, auction);
+		Self::release_seller_deposit(id, &auction.owner);
+		Ok(sale)
+	}
+
+	/// Block from which auction `id` no longer accepts bids and waits for its conclusion
+	fn ends_at(id: T::AuctionId, auction: &AuctionInfoOf<T>) -> T::BlockNumber {
+		Self::auction_end_by_id(id).unwrap_or(auction.end)
+	}
+
+	/// Schedules the conclusion of auction `id` at block `end`, replacing the previous schedule
+	fn schedule_end(id: T::AuctionId, end: T::BlockNumber) {
+		if let Some(previous) = <AuctionEndById<T>>::get(id) {
+			if previous == end {
+				return;
+			}
+			<AuctionEndTime<T>>::remove(previous, id);
+		}
+		<AuctionEndTime<T>>::insert(end, id, ());
+		<AuctionEndById<T>>::insert(id, end);
+	}
+
+	/// Removes auction `id` together with its index entries and bid history, returning its storage deposit
+	fn remove_auction_entries(id: T::AuctionId, auction: &AuctionInfoOf<T>) {
+		<Auctions<T>>::remove(id);
+		<AuctionOwnerById<T>>::remove(id);
+		<AuctionsByOwner<T>>::remove(&auction.owner, id);
+		for token in Self::auction_tokens(auction) {
+			<AuctionByToken<T>>::remove(token.0, token.1);
+		}
+		<BidHistory<T>>::remove_prefix(id);
+		<BidHistoryBounds<T>>::remove(id);
+		<ReserveCommitments<T>>::remove(id);
+		Self::unschedule_end(id);
+		T::Currency::unreserve(&auction.owner, <StorageDeposits<T>>::take(id));
+	}
+
+	/// Removes the scheduled conclusion of auction `id`
+	fn unschedule_end(id: T::AuctionId) {
+		if let Some(previous) = <AuctionEndById<T>>::take(id) {
+			<AuctionEndTime<T>>::remove(previous, id);
+		}
+	}
+
+	/// Concludes the queued auctions followed by the ones ending at `now`, at most
+	/// `MaxConclusionsPerBlock` of them, and queues the rest. Returns the weight consumed.
+	fn conclude_auction(now: T::BlockNumber) -> Weight {
+		let limit = T::MaxConclusionsPerBlock::get() as usize;
+		let bounds = <ConclusionQueueBounds<T>>::get();
+		let (mut head, mut tail) = bounds;
+		let mut due = Vec::new();
+		while head != tail && due.len() < limit {
+			if let Some(auction_id) = <ConclusionQueue<T>>::take(head) {
+				due.push(auction_id);
+			}
+			head = head.wrapping_add(1);
+		}
+		let mut ended: Weight = 0;
 		for (auction_id, _) in <AuctionEndTime<T>>::drain_prefix(&now) {
-			if let Some(auction) = Self::auctions(auction_id) {
-				pallet_nft::Module::<T>::toggle_lock(&auction.owner, auction.token_id).unwrap_or_default();
-				// there is a bid so let's determine a winner and transfer tokens
-				if let Some(ref winner) = auction.last_bid {
-					let dest = T::Lookup::unlookup(winner.0.clone());
-					let source = T::Origin::from(frame_system::RawOrigin::Signed(auction.owner.clone()));
-					pallet_nft::Module::<T>::transfer(source, dest, auction.token_id).unwrap_or_default();
-					T::Currency::remove_lock(AUCTION_LOCK_ID, &winner.0);
-					<T::Currency as Currency<T::AccountId>>::transfer(
-						&winner.0,
-						&auction.owner,
-						winner.1,
-						ExistenceRequirement::KeepAlive,
-					)
-					.unwrap_or_default();
+			if due.len() < limit {
+				due.push(auction_id);
+			} else {
+				<ConclusionQueue<T>>::insert(tail, auction_id);
+				tail = tail.wrapping_add(1);
+			}
+			ended += 1;
+		}
+		if (head, tail) != bounds {
+			if head == tail {
+				<ConclusionQueueBounds<T>>::kill();
+			} else {
+				<ConclusionQueueBounds<T>>::put((head, tail));
+			}
+		}
+
+		let mut settlement_weight: Weight = 0;
+		for auction_id in due.iter() {
+			if let Some(auction) = <Auctions<T>>::get(auction_id) {
+				settlement_weight = settlement_weight.saturating_add(Self::settlement_weight(*auction_id, &auction));
+				Self::try_settle_auction(*auction_id);
+			}
+		}
+		T::WeightInfo::on_initialize(due.len() as u32)
+			.saturating_add(settlement_weight)
+			.saturating_add(T::DbWeight::get().reads_writes(ended, ended.saturating_mul(2)))
+	}
+
+	/// Weight of settling or refunding the bids, paying the royalties and clearing the bid history of
+	/// auction `id`, on top of the weight of concluding an English auction
+	fn settlement_weight(id: T::AuctionId, auction: &AuctionInfoOf<T>) -> Weight {
+		let items = Self::auction_tokens(auction).count() as u32;
+		let bids_weight = match auction.auction_type {
+			AuctionType::TopUp => T::WeightInfo::settle_bids(<TopUpBids<T>>::decode_len(id).unwrap_or_default() as u32),
+			AuctionType::SealedFirstPrice | AuctionType::Vickrey => {
+				T::WeightInfo::settle_bids(Self::sealed_bid_count(id))
+			}
+			AuctionType::Candle => {
+				T::WeightInfo::settle_candle(auction.ending_period.unwrap_or_default().saturated_into::<u32>())
+			}
+			AuctionType::Combinatorial => {
+				T::WeightInfo::settle_bundle(items, <BundleBids<T>>::decode_len(id).unwrap_or_default() as u32)
+			}
+			_ => 0,
+		};
+		bids_weight
+			.saturating_add(T::WeightInfo::pay_royalties(items))
+			.saturating_add(Self::bid_history_weight(id))
+	}
+
+	/// Weight of clearing the bid history of auction `id`, a write for each of its records
+	fn bid_history_weight(id: T::AuctionId) -> Weight {
+		T::DbWeight::get().writes(Self::bid_history_bounds(id).1 as Weight)
+	}
+
+	/// Settlement weight of the most complex auction allowed by the configured bounds
+	fn max_settlement_weight() -> Weight {
+		let items = T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS);
+		T::WeightInfo::settle_bids(T::MaxTopUpBids::get().max(T::MaxSealedBids::get()))
+			.max(T::WeightInfo::settle_candle(
+				T::MaxCandleEndingPeriod::get().saturated_into::<u32>(),
+			))
+			.max(T::WeightInfo::settle_bundle(items, T::MaxBundleBids::get()))
+			.saturating_add(T::WeightInfo::pay_royalties(items))
+			.saturating_add(T::DbWeight::get().writes(T::MaxBidHistory::get() as Weight))
+	}
+
+	/// Settles auction `id`, a failed settlement is rolled back and queued to be retried
+	fn try_settle_auction(id: T::AuctionId) {
+		match Self::settle_auction(id) {
+			Ok(()) => <FailedSettlements<T>>::remove(id),
+			Err(error) => {
+				<FailedSettlements<T>>::insert(id, error);
+				Self::deposit_event(Event::SettlementFailed(id, error));
+			}
+		}
+	}
+
+	/// Hands the tokens to the winners and pays the owner, either completely or not at all
+	#[transactional]
+	fn settle_auction(auction_id: T::AuctionId) -> DispatchResult {
+		let auction = <Auctions<T>>::get(auction_id).ok_or(Error::<T>::AuctionNotExist)?;
+		Self::remove_auction_entries(auction_id, &auction);
+		Self::release_seller_deposit(auction_id, &auction.owner);
+		Self::toggle_token_locks(&auction)?;
+		let winner = match auction.auction_type {
+			AuctionType::Candle => Self::candle_winner(auction_id, &auction),
+			AuctionType::SealedFirstPrice | AuctionType::Vickrey => Self::sealed_winner(auction_id, &auction),
+			_ => auction.last_bid.clone(),
+		};
+		// Winning bid below the reserve price does not buy the token
+		let reserve_met = match (auction.reserve_price, &winner) {
+			(Some(reserve), Some((_, amount))) => *amount >= reserve,
+			(Some(_), None) => false,
+			(None, _) => true,
+		};
+		let winner = if reserve_met {
+			winner
+		} else {
+			if let Some((bidder, _)) = winner {
+				Self::release_bid_funds(&bidder, auction_id, auction.currency_id);
+			}
+			None
+		};
+		let (mut proceeds, mut commission) = (Zero::zero(), Zero::zero());
+		let mut sold = winner.is_some();
+		// there is a bid so let's determine a winner and transfer tokens
+		if let Some(ref winner) = winner {
+			let dest = T::Lookup::unlookup(winner.0.clone());
+			let source = T::Origin::from(frame_system::RawOrigin::Signed(auction.owner.clone()));
+			pallet_nft::Module::<T>::transfer(source, dest, auction.token_id).map_err(|e| e.error)?;
+			let sale = Self::pay_sale(
+				&winner.0,
+				auction_id,
+				&auction.owner,
+				&[auction.token_id],
+				winner.1,
+				auction.currency_id,
+			)?;
+			proceeds = sale.0;
+			commission = sale.1;
+		}
+		if auction.auction_type == AuctionType::Combinatorial {
+			if let Some((bundles_proceeds, bundles_commission)) = Self::settle_bundles(auction_id, &auction)? {
+				proceeds = proceeds.saturating_add(bundles_proceeds);
+				commission = commission.saturating_add(bundles_commission);
+				sold = true;
+			}
+		}
+		// Outbid participants of a top-up auction pay their fees and get the rest back
+		let top_up_bids = <TopUpBids<T>>::take(auction_id);
+		if !top_up_bids.is_empty() {
+			Self::charge_top_up_fees(auction_id, &auction, &top_up_bids)?;
+			for (bidder, _) in top_up_bids.iter() {
+				Self::release_bid_funds(bidder, auction_id, auction.currency_id);
+			}
+		}
+		// Revealed bids get their funds back, unrevealed ones lose the deposit
+		<SealedBidCount<T>>::remove(auction_id);
+		for (bidder, sealed_bid) in <SealedBids<T>>::drain_prefix(auction_id) {
+			if sealed_bid.revealed.is_some() {
+				Self::release_bid_funds(&bidder, auction_id, auction.currency_id);
+			} else {
+				let slashed = Self::slash_bid_funds(&bidder, auction_id, auction.currency_id);
+				Self::deposit_event(Event::SealedBidDepositSlashed(auction_id, bidder, slashed));
+			}
+		}
+		// Every leader of the ending period kept its funds reserved until now
+		for (_, (bidder, _)) in <CandleSnapshots<T>>::drain_prefix(auction_id) {
+			Self::release_bid_funds(&bidder, auction_id, auction.currency_id);
+		}
+		<CandleBidders<T>>::remove_prefix(auction_id);
+		if sold {
+			Self::deposit_event(Event::AuctionConcluded(auction_id, proceeds, commission));
+		} else {
+			Self::deposit_event(Event::AuctionConcludedWithoutSale(auction_id));
+		}
+		Ok(())
+	}
+
+	/// First block of the ending period of a candle auction
+	fn candle_ending_start(auction: &AuctionInfoOf<T>) -> Option<T::BlockNumber> {
+		match auction.auction_type {
+			AuctionType::Candle => auction.ending_period.map(|period| auction.end.saturating_sub(period)),
+			_ => None,
+		}
+	}
+
+	/// Randomly picks the block in the ending period at which the candle went out and returns the bid
+	/// that was leading at that block
+	fn candle_winner(id: T::AuctionId, auction: &AuctionInfoOf<T>) -> Option<(T::AccountId, BalanceOf<T>)> {
+		let (ending_start, period) = match (Self::candle_ending_start(auction), auction.ending_period) {
+			(Some(ending_start), Some(period)) if !period.is_zero() => (ending_start, period),
+			_ => return auction.last_bid.clone(),
+		};
+		let random = T::Randomness::random(&(CANDLE_RANDOM_SUBJECT, id).encode());
+		let seed = u32::decode(&mut random.as_ref()).unwrap_or_default();
+		let offset = seed % period.saturated_into::<u32>().max(1);
+		let closed_at = ending_start + offset.into();
+		Self::deposit_event(Event::CandleAuctionClosed(id, closed_at));
+
+		// Nobody bid during the ending period so the last bid stands
+		if <CandleSnapshots<T>>::iter_prefix(id).next().is_none() {
+			return auction.last_bid.clone();
+		}
+		<CandleSnapshots<T>>::iter_prefix(id)
+			.filter(|(block, _)| *block <= closed_at)
+			.max_by_key(|(block, _)| *block)
+			.map(|(_, bid)| bid)
+	}
+
+	/// Charges every outbid participant of a top-up auction the difference between its bid and the
+	/// closest lower one. The first and the winning bid pay no fee.
+	fn charge_top_up_fees(
+		id: T::AuctionId,
+		auction: &AuctionInfoOf<T>,
+		bids: &[(T::AccountId, BalanceOf<T>)],
+	) -> DispatchResult {
+		let beneficiary = auction.beneficiary.as_ref().unwrap_or(&auction.owner);
+		for pair in bids.windows(2).take(bids.len().saturating_sub(2)) {
+			let (bidder, amount) = &pair[1];
+			let fee = amount.saturating_sub(pair[0].1);
+			Self::pay_from_reserved(bidder, id, beneficiary, fee, auction.currency_id)?;
+			Self::deposit_event(Event::TopUpFeePaid(id, bidder.clone(), fee));
+		}
+		Ok(())
+	}
+
+	/// Every token offered by an auction, `token_id` first
+	fn auction_tokens(auction: &AuctionInfoOf<T>) -> impl Iterator<Item = (NftClassIdOf<T>, NftTokenIdOf<T>)> + '_ {
+		iter::once(auction.token_id).chain(auction.bundle.iter().copied())
+	}
+
+	/// Locks or unlocks every token of an auction
+	fn toggle_token_locks(auction: &AuctionInfoOf<T>) -> DispatchResult {
+		for token in Self::auction_tokens(auction) {
+			pallet_nft::Module::<T>::toggle_lock(&auction.owner, token)?;
+		}
+		Ok(())
+	}
+
+	fn place_bundle_bid(bidder: T::AccountId, id: T::AuctionId, items: u32, value: BalanceOf<T>) -> DispatchResult {
+		let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+		let block_number = frame_system::Module::<T>::block_number();
+		ensure!(
+			auction.auction_type == AuctionType::Combinatorial,
+			Error::<T>::BidNotAccepted
+		);
+		ensure!(bidder != auction.owner, Error::<T>::BidOnOwnAuction);
+		ensure!(Self::has_started(&auction, block_number), Error::<T>::AuctionNotStarted);
+		ensure!(block_number < auction.end, Error::<T>::AuctionAlreadyConcluded);
+		let item_count = auction.bundle.len() as u32 + 1;
+		ensure!(items != 0 && items >> item_count == 0, Error::<T>::InvalidBundle);
+		ensure!(
+			!value.is_zero() && value >= auction.minimal_bid,
+			Error::<T>::InvalidBidPrice
+		);
+
+		<BundleBids<T>>::try_mutate(id, |bids| -> DispatchResult {
+			// Bidding again on the same bundle raises the previous bid
+			if let Some(bid) = bids.iter_mut().find(|bid| bid.bidder == bidder && bid.items == items) {
+				ensure!(value > bid.amount, Error::<T>::InvalidBidPrice);
+				Self::reserve_bid_funds(&bidder, id, value - bid.amount, auction.currency_id)?;
+				bid.amount = value;
+			} else {
+				ensure!(
+					(bids.len() as u32) < T::MaxBundleBids::get(),
+					Error::<T>::TooManyBundleBids
+				);
+				Self::reserve_bid_funds(&bidder, id, value, auction.currency_id)?;
+				bids.push(BundleBid {
+					bidder: bidder.clone(),
+					items,
+					amount: value,
+				});
+			}
+			Ok(())
+		})?;
+		Self::record_bid(id, bidder, value);
+		Ok(())
+	}
+
+	/// Indices of the non-overlapping bundle bids with the highest total amount
+	fn best_bundle_allocation(bids: &[BundleBidOf<T>], item_count: u32) -> Vec<usize> {
+		let size = 1usize << item_count;
+		// Best revenue from selling items of each subset and the bid taking the lowest item of it
+		let mut best: Vec<(BalanceOf<T>, Option<usize>)> = vec![(Zero::zero(), None); size];
+		for mask in 1..size as u32 {
+			let lowest = mask & mask.wrapping_neg();
+			let mut choice = (best[(mask & !lowest) as usize].0, None);
+			for (index, bid) in bids.iter().enumerate() {
+				if bid.items & lowest != 0 && bid.items & !mask == 0 {
+					let revenue = bid.amount.saturating_add(best[(mask & !bid.items) as usize].0);
+					if revenue > choice.0 {
+						choice = (revenue, Some(index));
+					}
+				}
+			}
+			best[mask as usize] = choice;
+		}
+
+		let mut winners = Vec::new();
+		let mut mask = (size - 1) as u32;
+		while mask != 0 {
+			let lowest = mask & mask.wrapping_neg();
+			match best[mask as usize].1 {
+				Some(index) => {
+					winners.push(index);
+					mask &= !bids[index].items;
+				}
+				None => mask &= !lowest,
+			}
+		}
+		winners
+	}
+
+	/// Hands every winning bundle to its bidder and releases funds of all bids. Returns the amount
+	/// the owner kept and the commission charged on the sales, if any bundle was won.
+	fn settle_bundles(
+		id: T::AuctionId,
+		auction: &AuctionInfoOf<T>,
+	) -> result::Result<Option<(BalanceOf<T>, BalanceOf<T>)>, DispatchError> {
+		let bids = <BundleBids<T>>::take(id);
+		let (mut proceeds, mut commission) = (BalanceOf::<T>::zero(), BalanceOf::<T>::zero());
+		let tokens: Vec<_> = Self::auction_tokens(auction).collect();
+		let winners = Self::best_bundle_allocation(&bids, tokens.len() as u32);
+		for index in winners.iter().copied() {
+			let bid = &bids[index];
+			let won: Vec<_> = tokens
+				.iter()
+				.enumerate()
+				.filter(|(item, _)| bid.items & (1 << item) != 0)
+				.map(|(_, token)| *token)
+				.collect();
+			for token in won.iter() {
+				let dest = T::Lookup::unlookup(bid.bidder.clone());
+				let source = T::Origin::from(frame_system::RawOrigin::Signed(auction.owner.clone()));
+				pallet_nft::Module::<T>::transfer(source, dest, *token).map_err(|e| e.error)?;
+			}
+			let sale = Self::pay_sale(&bid.bidder, id, &auction.owner, &won, bid.amount, auction.currency_id)?;
+			proceeds = proceeds.saturating_add(sale.0);
+			commission = commission.saturating_add(sale.1);
+			Self::deposit_event(Event::BundleWon(id, bid.bidder.clone(), bid.items, bid.amount));
+		}
+		for bid in bids.iter() {
+			Self::release_bid_funds(&bid.bidder, id, auction.currency_id);
+		}
+		Ok(if winners.is_empty() {
+			None
+		} else {
+			Some((proceeds, commission))
+		})
+	}
+
+	/// First block of the reveal phase of a sealed auction
+	fn reveal_start(auction: &AuctionInfoOf<T>) -> Option<T::BlockNumber> {
+		match auction.auction_type {
+			AuctionType::SealedFirstPrice | AuctionType::Vickrey => {
+				auction.reveal_period.map(|period| auction.end.saturating_sub(period))
+			}
+			_ => None,
+		}
+	}
+
+	fn commit_sealed_bid(bidder: T::AccountId, id: T::AuctionId, commitment: T::Hash) -> DispatchResult {
+		let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+		let reveal_start = Self::reveal_start(&auction).ok_or(Error::<T>::BidNotAccepted)?;
+		let block_number = frame_system::Module::<T>::block_number();
+		ensure!(bidder != auction.owner, Error::<T>::BidOnOwnAuction);
+		ensure!(
+			Self::has_started(&auction, block_number) && block_number < reveal_start,
+			Error::<T>::NotInCommitPhase
+		);
+
+		if !<SealedBids<T>>::contains_key(id, &bidder) {
+			let count = Self::sealed_bid_count(id);
+			ensure!(count < T::MaxSealedBids::get(), Error::<T>::TooManySealedBids);
+			Self::reserve_bid_funds(&bidder, id, T::SealedBidDeposit::get(), auction.currency_id)?;
+			<SealedBidCount<T>>::insert(id, count + 1);
+		}
+		// Committing again replaces the previous commitment
+		<SealedBids<T>>::insert(
+			id,
+			&bidder,
+			SealedBid {
+				commitment,
+				revealed: None,
+			},
+		);
+		Ok(())
+	}
+
+	fn reveal_sealed_bid(bidder: T::AccountId, id: T::AuctionId, value: BalanceOf<T>, salt: Vec<u8>) -> DispatchResult {
+		let auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotExist)?;
+		let reveal_start = Self::reveal_start(&auction).ok_or(Error::<T>::BidNotAccepted)?;
+		let block_number = frame_system::Module::<T>::block_number();
+		ensure!(
+			block_number >= reveal_start && block_number < auction.end,
+			Error::<T>::NotInRevealPhase
+		);
+
+		<SealedBids<T>>::try_mutate(id, &bidder, |sealed_bid| -> DispatchResult {
+			let sealed_bid = sealed_bid.as_mut().ok_or(Error::<T>::BidNotCommitted)?;
+			ensure!(sealed_bid.revealed.is_none(), Error::<T>::BidAlreadyRevealed);
+			ensure!(
+				T::Hashing::hash_of(&(value, salt)) == sealed_bid.commitment,
+				Error::<T>::InvalidReveal
+			);
+			ensure!(
+				!value.is_zero() && value >= auction.minimal_bid,
+				Error::<T>::InvalidBidPrice
+			);
+			// Revealed bid has to be fully backed until the auction is settled
+			Self::reserve_bid_funds(&bidder, id, value, auction.currency_id)?;
+			sealed_bid.revealed = Some((value, block_number));
+			Ok(())
+		})?;
+		Self::record_bid(id, bidder, value);
+		Ok(())
+	}
+
+	/// Highest revealed bid of a sealed auction, earlier reveals win ties. The winner of a Vickrey
+	/// auction pays the second highest revealed bid, or the minimal bid if nobody else revealed.
+	fn sealed_winner(id: T::AuctionId, auction: &AuctionInfoOf<T>) -> Option<(T::AccountId, BalanceOf<T>)> {
+		let mut revealed: Vec<(T::AccountId, BalanceOf<T>, T::BlockNumber)> = <SealedBids<T>>::iter_prefix(id)
+			.filter_map(|(bidder, sealed_bid)| sealed_bid.revealed.map(|(value, at)| (bidder, value, at)))
+			.collect();
+		revealed.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+		let mut ranked = revealed.into_iter();
+		let (winner, highest, _) = ranked.next()?;
+		let price = match auction.auction_type {
+			// The second price never goes below the reserve, unless the winner itself bid less
+			AuctionType::Vickrey => {
+				let floor = auction.reserve_price.unwrap_or(auction.minimal_bid);
+				ranked
+					.next()
+					.map_or(floor, |(_, second, _)| second.max(floor))
+					.min(highest)
+			}
+			_ => highest,
+		};
+		Self::deposit_event(Event::SealedAuctionWon(id, winner.clone(), price));
+		Some((winner, price))
+	}
+
+	/// Reserves `amount` from `who` and records it as backing a bid in auction `id`
+	fn reserve_bid_funds(
+		who: &T::AccountId,
+		id: T::AuctionId,
+		amount: BalanceOf<T>,
+		currency: Option<T::CurrencyId>,
+	) -> DispatchResult {
+		Self::reserve_in(currency, who, amount)?;
+		<ReservedBids<T>>::try_mutate(who, id, |reserved| -> DispatchResult {
+			*reserved = reserved.checked_add(&amount).ok_or(Error::<T>::BidOverflow)?;
+			Ok(())
+		})?;
+		<AuctionsByBidder<T>>::insert(who, id, ());
+		Ok(())
+	}
+
+	/// Releases all funds `who` has reserved for auction `id`
+	fn release_bid_funds(who: &T::AccountId, id: T::AuctionId, currency: Option<T::CurrencyId>) {
+		let reserved = <ReservedBids<T>>::take(who, id);
+		<AuctionsByBidder<T>>::remove(who, id);
+		Self::unreserve_in(currency, who, reserved);
+	}
+
+	/// Records a snapshot of `who` at block `now` of candle auction `id`, keeping its highest bid of
+	/// the earlier blocks which was not replaced by another bidder
+	fn note_candle_bidder(id: T::AuctionId, who: &T::AccountId, now: T::BlockNumber) {
+		<CandleBidders<T>>::mutate(id, who, |entry| {
+			let earlier = match entry.take() {
+				// Bids only increase, so its latest surviving snapshot holds its highest bid
+				Some((latest, earlier)) if latest != now => <CandleSnapshots<T>>::get(id, latest)
+					.filter(|(bidder, _)| bidder == who)
+					.map(|(_, amount)| amount)
+					.or(earlier),
+				Some((_, earlier)) => earlier,
+				None => None,
+			};
+			*entry = Some((now, earlier));
+		});
+	}
+
+	/// Releases the funds `who` has reserved for candle auction `id` beyond its highest bid still
+	/// kept in a snapshot, all of them if it has none left. Only its snapshot of the current block
+	/// can have been replaced.
+	fn release_unbacked_candle_funds(who: &T::AccountId, id: T::AuctionId, currency: Option<T::CurrencyId>) {
+		let backed = <CandleBidders<T>>::get(id, who).and_then(|(_, earlier)| earlier);
+		match backed {
+			Some(amount) => {
+				let reserved = <ReservedBids<T>>::get(who, id);
+				if reserved > amount {
+					Self::unreserve_in(currency, who, reserved - amount);
+					<ReservedBids<T>>::insert(who, id, amount);
+				}
+			}
+			None => {
+				// Only bidders kept in a snapshot are tracked, so there is one per block at most
+				<CandleBidders<T>>::remove(id, who);
+				Self::release_bid_funds(who, id, currency);
+			}
+		}
+	}
+
+	/// Adds a bid to the history of auction `id`, dropping the oldest one when it is full
+	fn record_bid(id: T::AuctionId, bidder: T::AccountId, amount: BalanceOf<T>) {
+		let max_history = T::MaxBidHistory::get();
+		if max_history == 0 {
+			return;
+		}
+		let (next, len) = Self::bid_history_bounds(id);
+		let record = BidRecord {
+			bidder,
+			amount,
+			block: frame_system::Module::<T>::block_number(),
+		};
+		<BidHistory<T>>::insert(id, next, record);
+		<BidHistoryBounds<T>>::insert(id, ((next + 1) % max_history, len.saturating_add(1).min(max_history)));
+	}
+
+	/// Releases the funds of every bidder of auction `id`
+	fn release_all_bid_funds(id: T::AuctionId, auction: &AuctionInfoOf<T>) {
+		if let Some((leader, _)) = &auction.last_bid {
+			Self::release_bid_funds(leader, id, auction.currency_id);
+		}
+		for (bidder, _) in <TopUpBids<T>>::take(id) {
+			Self::release_bid_funds(&bidder, id, auction.currency_id);
+		}
+		<SealedBidCount<T>>::remove(id);
+		for (bidder, _) in <SealedBids<T>>::drain_prefix(id) {
+			Self::release_bid_funds(&bidder, id, auction.currency_id);
+		}
+		for bid in <BundleBids<T>>::take(id) {
+			Self::release_bid_funds(&bid.bidder, id, auction.currency_id);
+		}
+		for (_, (bidder, _)) in <CandleSnapshots<T>>::drain_prefix(id) {
+			Self::release_bid_funds(&bidder, id, auction.currency_id);
+		}
+		<CandleBidders<T>>::remove_prefix(id);
+	}
+
+	/// Reserves the cancellation deposit of auction `id` from its owner
+	fn reserve_seller_deposit(id: T::AuctionId, owner: &T::AccountId) -> DispatchResult {
+		let deposit = T::CancellationPenalty::get();
+		if !deposit.is_zero() {
+			T::Currency::reserve(owner, deposit)?;
+			<SellerDeposits<T>>::insert(id, deposit);
+		}
+		Ok(())
+	}
+
+	/// Returns the cancellation deposit of auction `id` to its owner
+	fn release_seller_deposit(id: T::AuctionId, owner: &T::AccountId) {
+		let deposit = <SellerDeposits<T>>::take(id);
+		T::Currency::unreserve(owner, deposit);
+	}
+
+	/// Storage deposit of an auction with the given terms
+	fn storage_deposit(info: &AuctionInfoOf<T>) -> BalanceOf<T> {
+		let bytes = BalanceOf::<T>::from(info.encoded_size() as u32);
+		T::AuctionDepositBase::get().saturating_add(T::AuctionDepositPerByte::get().saturating_mul(bytes))
+	}
+
+	/// Reserves or returns funds of the owner until the storage deposit of auction `id` matches its terms
+	fn adjust_storage_deposit(id: T::AuctionId, info: &AuctionInfoOf<T>) -> DispatchResult {
+		let deposit = Self::storage_deposit(info);
+		let reserved = <StorageDeposits<T>>::get(id);
+		if deposit > reserved {
+			T::Currency::reserve(&info.owner, deposit - reserved)?;
+		} else {
+			T::Currency::unreserve(&info.owner, reserved - deposit);
+		}
+		if deposit.is_zero() {
+			<StorageDeposits<T>>::remove(id);
+		} else {
+			<StorageDeposits<T>>::insert(id, deposit);
+		}
+		Ok(())
+	}
+
+	/// Slashes all funds `who` has reserved for auction `id`
+	fn slash_bid_funds(who: &T::AccountId, id: T::AuctionId, currency: Option<T::CurrencyId>) -> BalanceOf<T> {
+		let reserved = <ReservedBids<T>>::take(who, id);
+		<AuctionsByBidder<T>>::remove(who, id);
+		let unslashed = Self::slash_reserved_in(currency, who, reserved);
+		reserved.saturating_sub(unslashed)
+	}
+
+	/// Moves `amount` out of the funds `who` has reserved for auction `id` to the free balance of `dest`
+	fn pay_from_reserved(
+		who: &T::AccountId,
+		id: T::AuctionId,
+		dest: &T::AccountId,
+		amount: BalanceOf<T>,
+		currency: Option<T::CurrencyId>,
+	) -> DispatchResult {
+		<ReservedBids<T>>::try_mutate_exists(who, id, |reserved| -> DispatchResult {
+			let remaining = reserved
+				.unwrap_or_default()
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::InsufficientReservedFunds)?;
+			let unpaid = Self::repatriate_reserved_in(currency, who, dest, amount)?;
+			ensure!(unpaid.is_zero(), Error::<T>::InsufficientReservedFunds);
+			*reserved = if remaining.is_zero() {
+				<AuctionsByBidder<T>>::remove(who, id);
+				None
+			} else {
+				Some(remaining)
+			};
+			Ok(())
+		})
+	}
+
+	/// Pays `amount` reserved by `who` for auction `id` to the owner, who is charged the commission
+	/// and the royalties of the sold tokens. Returns the amount the owner kept and the commission.
+	fn pay_sale(
+		who: &T::AccountId,
+		id: T::AuctionId,
+		owner: &T::AccountId,
+		tokens: &[(NftClassIdOf<T>, NftTokenIdOf<T>)],
+		amount: BalanceOf<T>,
+		currency: Option<T::CurrencyId>,
+	) -> result::Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		Self::pay_from_reserved(who, id, owner, amount, currency)?;
+		Self::charge_seller(id, owner, tokens, amount, currency)
+	}
+
+	/// Charges the owner, who was paid `amount` for `tokens` of auction `id`, the commission and the
+	/// royalties. Returns the amount the owner kept and the commission.
+	fn charge_seller(
+		id: T::AuctionId,
+		owner: &T::AccountId,
+		tokens: &[(NftClassIdOf<T>, NftTokenIdOf<T>)],
+		amount: BalanceOf<T>,
+		currency: Option<T::CurrencyId>,
+	) -> result::Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let commission = T::Commission::get().mul_floor(amount);
+		if !commission.is_zero() {
+			match currency {
+				Some(currency_id) => {
+					T::MultiCurrency::transfer(currency_id, owner, &T::TokenCommissionAccount::get(), commission)?
 				}
+				None => {
+					let fee = T::Currency::withdraw(
+						owner,
+						commission,
+						WithdrawReasons::FEE,
+						ExistenceRequirement::AllowDeath,
+					)?;
+					T::CommissionDestination::on_unbalanced(fee);
+				}
+			}
+		}
+		let royalties = Self::pay_royalties(id, owner, tokens, amount, currency)?;
+		Ok((amount.saturating_sub(commission).saturating_sub(royalties), commission))
+	}
+
+	/// Pays the royalties of the classes of `tokens` sold together for `price` from the funds of the
+	/// seller. Sales by the creator or the beneficiary of a class pay none. Returns the total paid.
+	fn pay_royalties(
+		id: T::AuctionId,
+		seller: &T::AccountId,
+		tokens: &[(NftClassIdOf<T>, NftTokenIdOf<T>)],
+		price: BalanceOf<T>,
+		currency: Option<T::CurrencyId>,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		let share = price / BalanceOf::<T>::from(tokens.len().max(1) as u32);
+		let mut paid = BalanceOf::<T>::zero();
+		for (class_id, _) in tokens.iter() {
+			let royalty = match pallet_nft::Module::<T>::secondary_sale_royalty(seller, *class_id) {
+				Some(royalty) => royalty,
+				None => continue,
+			};
+			let amount = royalty.rate.mul_floor(share);
+			if !amount.is_zero() {
+				Self::transfer_in(
+					currency,
+					seller,
+					&royalty.beneficiary,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				)?;
+				paid = paid.saturating_add(amount);
+				Self::deposit_event(Event::RoyaltyPaid(id, royalty.beneficiary, amount));
+			}
+		}
+		Ok(paid)
+	}
+
+	/// Reserves `amount` of `currency` from `who`, the native currency unless set
+	fn reserve_in(currency: Option<T::CurrencyId>, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		match currency {
+			Some(currency_id) => T::MultiCurrency::reserve(currency_id, who, amount),
+			None => T::Currency::reserve(who, amount),
+		}
+	}
+
+	/// Unreserves `amount` of `currency` of `who`
+	fn unreserve_in(currency: Option<T::CurrencyId>, who: &T::AccountId, amount: BalanceOf<T>) {
+		match currency {
+			Some(currency_id) => {
+				T::MultiCurrency::unreserve(currency_id, who, amount);
+			}
+			None => {
+				T::Currency::unreserve(who, amount);
+			}
+		}
+	}
+
+	/// Slashes `amount` of `currency` reserved by `who`, returns the part which could not be slashed
+	fn slash_reserved_in(currency: Option<T::CurrencyId>, who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		match currency {
+			Some(currency_id) => T::MultiCurrency::slash_reserved(currency_id, who, amount),
+			None => T::Currency::slash_reserved(who, amount).1,
+		}
+	}
+
+	/// Moves `amount` of `currency` reserved by `from` to the free balance of `to`, returns the part
+	/// which could not be moved
+	fn repatriate_reserved_in(
+		currency: Option<T::CurrencyId>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		match currency {
+			Some(currency_id) => {
+				T::MultiCurrency::repatriate_reserved(currency_id, from, to, amount, BalanceStatus::Free)
 			}
+			None => T::Currency::repatriate_reserved(from, to, amount, BalanceStatus::Free),
+		}
+	}
+
+	/// Transfers `amount` of `currency` from `from` to `to`, `existence` applies to the native currency only
+	fn transfer_in(
+		currency: Option<T::CurrencyId>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+		existence: ExistenceRequirement,
+	) -> DispatchResult {
+		match currency {
+			Some(currency_id) => T::MultiCurrency::transfer(currency_id, from, to, amount),
+			None => <T::Currency as Currency<T::AccountId>>::transfer(from, to, amount, existence),
 		}
 	}
 
@@ -213,27 +1685,125 @@ impl<T: Config> Pallet<T> {
 			info.start >= current_block_number,
 			Error::<T>::AuctionStartTimeAlreadyPassed
 		);
+		Self::check_auction_terms(info)?;
+		for token in Self::auction_tokens(info) {
+			let is_owner = pallet_nft::Module::<T>::is_owner(&info.owner, token);
+			ensure!(is_owner, Error::<T>::NotATokenOwner);
+			let nft_locked = pallet_nft::Module::<T>::is_locked(token)?;
+			ensure!(!nft_locked, Error::<T>::TokenLocked);
+		}
+		Ok(())
+	}
+
+	/// Checks the times, prices and bundle of an auction, shared by its creation and updates
+	fn check_auction_terms(info: &AuctionInfoOf<T>) -> DispatchResult {
 		ensure!(
-			info.start >= Zero::zero() && info.end > Zero::zero() && info.end > info.start + MIN_AUCTION_DUR.into(),
+			Self::never_expires(info)
+				|| (info.start >= Zero::zero()
+					&& info.end > Zero::zero()
+					&& info.end > info.start + T::MinAuctionDuration::get()),
 			Error::<T>::InvalidTimeConfiguration
 		);
 		ensure!(!info.name.is_empty(), Error::<T>::EmptyAuctionName);
-		let is_owner = pallet_nft::Module::<T>::is_owner(&info.owner, info.token_id);
-		ensure!(is_owner, Error::<T>::NotATokenOwner);
-		let nft_locked = pallet_nft::Module::<T>::is_locked(info.token_id)?;
-		ensure!(!nft_locked, Error::<T>::TokenLocked);
+		ensure!(
+			info.name.len() <= T::MaxNameLength::get() as usize,
+			Error::<T>::AuctionNameTooLong
+		);
+		if info.auction_type == AuctionType::Dutch {
+			let (amount, blocks) = info.price_decay.ok_or(Error::<T>::InvalidPriceConfiguration)?.rate();
+			ensure!(
+				info.starting_price > info.floor_price && !amount.is_zero() && !blocks.is_zero(),
+				Error::<T>::InvalidPriceConfiguration
+			);
+		}
+		if info.auction_type == AuctionType::FixedSwap {
+			ensure!(!info.starting_price.is_zero(), Error::<T>::InvalidPriceConfiguration);
+		}
+		ensure!(
+			info.reserve_price.is_none() || Self::accepts_reserve_price(info),
+			Error::<T>::InvalidPriceConfiguration
+		);
+		if let Some(buy_now_price) = info.buy_now_price {
+			ensure!(
+				info.auction_type == AuctionType::English
+					&& buy_now_price >= info.minimal_bid
+					&& info.reserve_price.map_or(true, |reserve| buy_now_price >= reserve),
+				Error::<T>::InvalidPriceConfiguration
+			);
+		}
+		if matches!(info.auction_type, AuctionType::SealedFirstPrice | AuctionType::Vickrey) {
+			let period = info.reveal_period.ok_or(Error::<T>::InvalidRevealPeriod)?;
+			ensure!(
+				!period.is_zero() && period < info.end - info.start,
+				Error::<T>::InvalidRevealPeriod
+			);
+		}
+		if info.auction_type == AuctionType::Candle {
+			let period = info.ending_period.ok_or(Error::<T>::InvalidEndingPeriod)?;
+			ensure!(
+				!period.is_zero() && period <= info.end - info.start && period <= T::MaxCandleEndingPeriod::get(),
+				Error::<T>::InvalidEndingPeriod
+			);
+		}
+		// Every bid has to raise the previous one by a step or by a fixed increment
+		let step = info.bid_step.unwrap_or_else(T::BidStep::get);
+		let has_increment = info.min_bid_increment.map_or(false, |increment| !increment.is_zero());
+		ensure!(
+			step <= T::MaxBidStep::get()
+				&& (has_increment || (!step.is_zero() && step >= T::MinBidStep::get()))
+				&& info
+					.anti_snipe_blocks
+					.map_or(true, |blocks| blocks <= T::MaxAntiSnipeBlocks::get()),
+			Error::<T>::InvalidBidRules
+		);
+		if let Some(window) = info.inactivity_window {
+			ensure!(
+				info.auction_type == AuctionType::English && !window.is_zero(),
+				Error::<T>::InvalidInactivityWindow
+			);
+		}
+		if info.auction_type == AuctionType::Combinatorial {
+			let item_count = info.bundle.len() as u32 + 1;
+			ensure!(
+				item_count <= T::MaxBundleItems::get().min(MAX_BUNDLE_ITEMS),
+				Error::<T>::InvalidBundle
+			);
+		} else {
+			ensure!(info.bundle.is_empty(), Error::<T>::InvalidBundle);
+		}
+		for (index, token) in info.bundle.iter().enumerate() {
+			ensure!(
+				*token != info.token_id && !info.bundle[..index].contains(token),
+				Error::<T>::InvalidBundle
+			);
+		}
 		Ok(())
 	}
+
+	/// Whether `new` ends no earlier than `old`, a listing which never expires can not get an end
+	fn end_not_earlier(old: &AuctionInfoOf<T>, new: &AuctionInfoOf<T>) -> bool {
+		match (Self::never_expires(old), Self::never_expires(new)) {
+			(true, expires_never) => expires_never,
+			(false, true) => true,
+			(false, false) => new.end >= old.end,
+		}
+	}
 }
 
 impl<T: Config> Auction<T::AccountId, T::BlockNumber, NftClassIdOf<T>, NftTokenIdOf<T>> for Pallet<T> {
 	type AuctionId = T::AuctionId;
 	type Balance = BalanceOf<T>;
 	type AccountId = T::AccountId;
+	type CurrencyId = T::CurrencyId;
 
-	fn new_auction(info: AuctionInfoOf<T>) -> result::Result<Self::AuctionId, DispatchError> {
+	#[transactional]
+	fn new_auction(mut info: AuctionInfoOf<T>) -> result::Result<Self::AuctionId, DispatchError> {
 		// Basic checks before an auction is created
 		Self::check_new_auction(&info)?;
+		// A Dutch auction is cancelled once its price drops to the floor
+		if let Some(floor_block) = Self::dutch_floor_block(&info) {
+			info.end = info.end.min(floor_block);
+		}
 		let auction_id = <NextAuctionId<T>>::try_mutate(|next_id| -> result::Result<Self::AuctionId, DispatchError> {
 			let current_id = *next_id;
 			*next_id = next_id
@@ -242,62 +1812,188 @@ impl<T: Config> Auction<T::AccountId, T::BlockNumber, NftClassIdOf<T>, NftTokenI
 			Ok(current_id)
 		})?;
 
+		Self::reserve_seller_deposit(auction_id, &info.owner)?;
+		Self::adjust_storage_deposit(auction_id, &info)?;
 		<Auctions<T>>::insert(auction_id, info.clone());
 		<AuctionOwnerById<T>>::insert(auction_id, &info.owner);
-		<AuctionEndTime<T>>::insert(info.end, auction_id, ());
-		pallet_nft::Module::<T>::toggle_lock(&info.owner, info.token_id).unwrap_or_default();
+		<AuctionsByOwner<T>>::insert(&info.owner, auction_id, ());
+		for token in Self::auction_tokens(&info) {
+			<AuctionByToken<T>>::insert(token.0, token.1, auction_id);
+		}
+		if !Self::never_expires(&info) {
+			Self::schedule_end(auction_id, info.end);
+		}
+		Self::toggle_token_locks(&info)?;
 
 		Ok(auction_id)
 	}
 
-	fn update_auction(id: Self::AuctionId, info: AuctionInfoOf<T>) -> DispatchResult {
-		<Auctions<T>>::try_mutate(id, |auction| -> DispatchResult {
-			ensure!(auction.is_some(), Error::<T>::AuctionNotExist);
-			*auction = Some(info);
-			Ok(())
-		})
+	/// Before the start any terms of the auction can change, afterwards the end can only be
+	/// postponed and the reserve price lowered. The owner and the tokens never change.
+	#[transactional]
+	fn update_auction(id: Self::AuctionId, mut info: AuctionInfoOf<T>) -> DispatchResult {
+		let auction = <Auctions<T>>::get(id).ok_or(Error::<T>::AuctionNotExist)?;
+		ensure!(
+			info.owner == auction.owner && info.token_id == auction.token_id && info.bundle == auction.bundle,
+			Error::<T>::InvalidAuctionUpdate
+		);
+		info.last_bid = auction.last_bid.clone();
+
+		let current_block_number = frame_system::Module::<T>::block_number();
+		if !Self::has_started(&auction, current_block_number) {
+			ensure!(
+				info.start >= current_block_number,
+				Error::<T>::AuctionStartTimeAlreadyPassed
+			);
+		} else {
+			// An ended auction waiting for its settlement can not be revived
+			ensure!(
+				current_block_number < Self::ends_at(id, &auction) || Self::never_expires(&auction),
+				Error::<T>::AuctionAlreadyConcluded
+			);
+			// The phases of candle and sealed auctions are derived from their end and a running
+			// inactivity window already scheduled an earlier one
+			let end_movable = !matches!(
+				auction.auction_type,
+				AuctionType::Candle | AuctionType::SealedFirstPrice | AuctionType::Vickrey
+			) && (auction.inactivity_window.is_none() || auction.last_bid.is_none());
+			let unchanged_terms = AuctionInfo {
+				end: auction.end,
+				reserve_price: auction.reserve_price,
+				..info.clone()
+			};
+			ensure!(
+				unchanged_terms == auction
+					&& (info.end == auction.end || (end_movable && Self::end_not_earlier(&auction, &info)))
+					&& info.reserve_price <= auction.reserve_price,
+				Error::<T>::InvalidAuctionUpdate
+			);
+		}
+		Self::check_auction_terms(&info)?;
+		if let Some(floor_block) = Self::dutch_floor_block(&info) {
+			info.end = info.end.min(floor_block);
+		}
+
+		if info.end != auction.end {
+			Self::unschedule_end(id);
+			if !Self::never_expires(&info) {
+				Self::schedule_end(id, info.end);
+			}
+		}
+		let extended_to = Some(info.end).filter(|end| *end > auction.end && !Self::never_expires(&info));
+		Self::adjust_storage_deposit(id, &info)?;
+		<Auctions<T>>::insert(id, info);
+		if let Some(end) = extended_to {
+			Self::deposit_event(Event::AuctionExtended(id, end));
+		}
+		Ok(())
 	}
 
+	#[transactional]
 	fn remove_auction(id: Self::AuctionId) -> DispatchResult {
-		let auction = <Auctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExist)?;
+		let auction = <Auctions<T>>::get(id).ok_or(Error::<T>::AuctionNotExist)?;
 		let current_block_number = frame_system::Module::<T>::block_number();
-		ensure!(current_block_number < auction.start, Error::<T>::AuctionAlreadyStarted);
-		pallet_nft::Module::<T>::toggle_lock(&auction.owner, auction.token_id).unwrap_or_default();
-		<AuctionOwnerById<T>>::remove(id);
-		<Auctions<T>>::remove(id);
+		// Fixed price listings can be withdrawn at any time
+		ensure!(
+			!Self::has_started(&auction, current_block_number) || auction.auction_type == AuctionType::FixedSwap,
+			Error::<T>::AuctionAlreadyStarted
+		);
+		Self::toggle_token_locks(&auction)?;
+		Self::remove_auction_entries(id, &auction);
+		Self::release_seller_deposit(id, &auction.owner);
 		Ok(())
 	}
 
+	#[transactional]
 	fn bid(bidder: Self::AccountId, id: Self::AuctionId, value: Self::Balance) -> DispatchResult {
 		<Auctions<T>>::try_mutate_exists(id, |auction| -> DispatchResult {
 			// Basic checks before a bid can be made
 			let mut auction = auction.as_mut().ok_or(Error::<T>::AuctionNotExist)?;
 			let block_number = <frame_system::Module<T>>::block_number();
+			ensure!(
+				matches!(
+					auction.auction_type,
+					AuctionType::English | AuctionType::Candle | AuctionType::TopUp
+				),
+				Error::<T>::BidNotAccepted
+			);
 			ensure!(bidder != auction.owner, Error::<T>::BidOnOwnAuction);
-			ensure!(block_number > auction.start, Error::<T>::AuctionNotStarted);
-			ensure!(block_number < auction.end, Error::<T>::AuctionAlreadyConcluded);
+			ensure!(Self::has_started(auction, block_number), Error::<T>::AuctionNotStarted);
+			ensure!(
+				block_number < Self::ends_at(id, auction),
+				Error::<T>::AuctionAlreadyConcluded
+			);
 			ensure!(value >= auction.minimal_bid, Error::<T>::InvalidBidPrice);
 			if let Some(ref current_bid) = auction.last_bid {
 				ensure!(value > current_bid.1, Error::<T>::InvalidBidPrice);
-				// Unlock funds from the previous bid
-				T::Currency::remove_lock(AUCTION_LOCK_ID, &current_bid.0);
 			} else {
 				ensure!(!value.is_zero(), Error::<T>::InvalidBidPrice);
 			}
-			// Lock funds
-			T::Currency::set_lock(AUCTION_LOCK_ID, &bidder, value, WithdrawReasons::all());
-			auction.last_bid = Some((bidder, value));
+			match Self::candle_ending_start(auction) {
+				Some(ending_start) if block_number >= ending_start => {
+					// Any leader of the ending period may still win, so nobody gets their funds back yet
+					if let Some(ref current_bid) = auction.last_bid {
+						// Remember who was leading when the ending period started. The leader has no
+						// snapshot only until the first bid of the ending period.
+						if !<CandleBidders<T>>::contains_key(id, &current_bid.0) {
+							let before_ending = ending_start.saturating_sub(One::one());
+							<CandleSnapshots<T>>::insert(id, before_ending, current_bid.clone());
+							<CandleBidders<T>>::insert(id, &current_bid.0, (before_ending, None::<BalanceOf<T>>));
+						}
+					}
+					// A later bid in the same block replaces the snapshot of the earlier one
+					Self::note_candle_bidder(id, &bidder, block_number);
+					let overwritten = <CandleSnapshots<T>>::get(id, block_number);
+					<CandleSnapshots<T>>::insert(id, block_number, (bidder.clone(), value));
+					if let Some((previous, _)) = overwritten.filter(|(previous, _)| *previous != bidder) {
+						Self::release_unbacked_candle_funds(&previous, id, auction.currency_id);
+					}
+					let reserved = Self::reserved_bids(&bidder, id);
+					Self::reserve_bid_funds(&bidder, id, value.saturating_sub(reserved), auction.currency_id)?;
+				}
+				_ if auction.auction_type == AuctionType::TopUp => {
+					ensure!(
+						(<TopUpBids<T>>::decode_len(id).unwrap_or_default() as u32) < T::MaxTopUpBids::get(),
+						Error::<T>::TooManyTopUpBids
+					);
+					// Outbid participants pay a top-up fee at settlement, so every bid stays reserved
+					Self::reserve_bid_funds(&bidder, id, value, auction.currency_id)?;
+					<TopUpBids<T>>::append(id, (bidder.clone(), value));
+				}
+				_ => {
+					if let Some(ref current_bid) = auction.last_bid {
+						// Release funds backing the previous bid
+						Self::release_bid_funds(&current_bid.0, id, auction.currency_id);
+					}
+					// Reserve funds for this auction only
+					Self::reserve_bid_funds(&bidder, id, value, auction.currency_id)?;
+				}
+			}
+			auction.last_bid = Some((bidder.clone(), value));
+			Self::record_bid(id, bidder, value);
 			// Set next minimal bid
-			let minimal_bid_step = Permill::from_percent(BID_STEP_PERC).mul_floor(value);
+			let minimal_bid_step = auction
+				.bid_step
+				.unwrap_or_else(T::BidStep::get)
+				.mul_floor(value)
+				.max(auction.min_bid_increment.unwrap_or_else(Zero::zero));
 			auction.minimal_bid = value.checked_add(&minimal_bid_step).ok_or(Error::<T>::BidOverflow)?;
 			// Avoid auction sniping
 			let time_left = auction
 				.end
 				.checked_sub(&block_number)
 				.ok_or(Error::<T>::TimeUnderflow)?;
-			if time_left < BID_ADD_BLOCKS.into() {
-				auction.end = block_number + BID_ADD_BLOCKS.into();
+			let anti_snipe_blocks = auction.anti_snipe_blocks.unwrap_or_else(T::AntiSnipeBlocks::get);
+			if auction.auction_type != AuctionType::Candle && time_left < anti_snipe_blocks {
+				auction.end = block_number + anti_snipe_blocks;
+				Self::deposit_event(Event::AuctionExtended(id, auction.end));
 			}
+			// Without a better bid within the inactivity window the auction ends early
+			let end = match auction.inactivity_window {
+				Some(window) => auction.end.min(block_number.saturating_add(window)),
+				None => auction.end,
+			};
+			Self::schedule_end(id, end);
 			Ok(())
 		})
 	}
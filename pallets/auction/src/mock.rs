@@ -1,11 +1,17 @@
 use crate as pallet_auction;
-use frame_support::parameter_types;
+use frame_support::{
+	parameter_types,
+	traits::{Currency, Get, OnUnbalanced, Randomness},
+};
 use frame_system as system;
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Permill,
 };
+use std::cell::RefCell;
 
 mod auction {
 	// Re-export needed for `impl_outer_event!`.
@@ -25,22 +31,35 @@ frame_support::construct_runtime!(
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
 		Auctions: pallet_auction::{Module, Call, Storage, Event<T>},
 		OrmlNft: orml_nft::{Module, Storage, Config<T>},
-		Nft: pallet_nft::{Module, Call, Event<T>},
+		Nft: pallet_nft::{Module, Call, Storage, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
 	}
 );
 
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of a token besides the native currency.
+pub type CurrencyId = u32;
+
+/// Tokens auctions can be held in
+pub const DOT: CurrencyId = 1;
+pub const KSM: CurrencyId = 2;
+
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
 }
 
+parameter_types! {
+	pub const MaxRoyalty: Permill = Permill::from_percent(50);
+}
+
 impl pallet_nft::Config for Test {
 	type Event = Event;
 	type WeightInfo = pallet_nft::weights::SubstrateWeight<Test>;
+	type MaxRoyalty = MaxRoyalty;
 }
 
 impl orml_nft::Config for Test {
@@ -50,11 +69,144 @@ impl orml_nft::Config for Test {
 	type TokenData = pallet_nft::TokenData;
 }
 
+thread_local! {
+	static RANDOM_SEED: RefCell<u32> = RefCell::new(0);
+	static CANCELLATION_PENALTY: RefCell<Balance> = RefCell::new(0);
+	static AUCTION_DEPOSIT: RefCell<(Balance, Balance)> = RefCell::new((0, 0));
+	static COMMISSION: RefCell<Permill> = RefCell::new(Permill::zero());
+}
+
+/// Randomness source whose output is controlled by the tests
+pub struct TestRandomness;
+
+impl TestRandomness {
+	pub fn set_seed(seed: u32) {
+		RANDOM_SEED.with(|s| *s.borrow_mut() = seed);
+	}
+}
+
+impl Randomness<H256> for TestRandomness {
+	fn random(_subject: &[u8]) -> H256 {
+		let mut random = H256::zero();
+		let seed = RANDOM_SEED.with(|s| *s.borrow());
+		random.as_bytes_mut()[..4].copy_from_slice(&seed.to_le_bytes());
+		random
+	}
+}
+
+/// Seller deposit of new auctions, none unless a test sets it
+pub struct CancellationPenalty;
+
+impl CancellationPenalty {
+	pub fn set(penalty: Balance) {
+		CANCELLATION_PENALTY.with(|p| *p.borrow_mut() = penalty);
+	}
+}
+
+impl Get<Balance> for CancellationPenalty {
+	fn get() -> Balance {
+		CANCELLATION_PENALTY.with(|p| *p.borrow())
+	}
+}
+
+/// Storage deposit of auctions, a base and a per-byte part
+pub struct AuctionDeposit;
+
+impl AuctionDeposit {
+	pub fn set(base: Balance, per_byte: Balance) {
+		AUCTION_DEPOSIT.with(|d| *d.borrow_mut() = (base, per_byte));
+	}
+}
+
+pub struct AuctionDepositBase;
+
+impl Get<Balance> for AuctionDepositBase {
+	fn get() -> Balance {
+		AUCTION_DEPOSIT.with(|d| d.borrow().0)
+	}
+}
+
+pub struct AuctionDepositPerByte;
+
+impl Get<Balance> for AuctionDepositPerByte {
+	fn get() -> Balance {
+		AUCTION_DEPOSIT.with(|d| d.borrow().1)
+	}
+}
+
+pub struct Commission;
+
+impl Commission {
+	pub fn set(commission: Permill) {
+		COMMISSION.with(|c| *c.borrow_mut() = commission);
+	}
+}
+
+impl Get<Permill> for Commission {
+	fn get() -> Permill {
+		COMMISSION.with(|c| *c.borrow())
+	}
+}
+
+/// Account receiving the commission
+pub const FEE_ACCOUNT: u64 = 999;
+
+pub struct CommissionToFeeAccount;
+
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for CommissionToFeeAccount {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&FEE_ACCOUNT, amount);
+	}
+}
+
+parameter_types! {
+	pub const SealedBidDeposit: Balance = 10;
+	pub const MaxBundleItems: u32 = 4;
+	pub const MaxBundleBids: u32 = 5;
+	pub const MaxTopUpBids: u32 = 5;
+	pub const MaxSealedBids: u32 = 3;
+	pub const MaxConclusionsPerBlock: u32 = 2;
+	pub const MaxCandleEndingPeriod: u64 = 15;
+	pub const BidStep: Permill = Permill::from_percent(10);
+	pub const MinBidStep: Permill = Permill::from_percent(1);
+	pub const MaxBidStep: Permill = Permill::from_percent(50);
+	pub const AntiSnipeBlocks: u64 = 10;
+	pub const MaxAntiSnipeBlocks: u64 = 50;
+	pub const MinAuctionDuration: u64 = 10;
+	pub const MaxBidHistory: u32 = 3;
+	pub const MaxNameLength: u32 = 16;
+	pub const TokenCommissionAccount: u64 = FEE_ACCOUNT;
+}
+
 impl pallet_auction::Config for Test {
 	type Event = Event;
 	type Balance = Balance;
 	type AuctionId = u64;
 	type Currency = Balances;
+	type CurrencyId = CurrencyId;
+	type MultiCurrency = Tokens;
+	type Randomness = TestRandomness;
+	type SealedBidDeposit = SealedBidDeposit;
+	type MaxBundleItems = MaxBundleItems;
+	type MaxBundleBids = MaxBundleBids;
+	type MaxTopUpBids = MaxTopUpBids;
+	type MaxSealedBids = MaxSealedBids;
+	type MaxConclusionsPerBlock = MaxConclusionsPerBlock;
+	type MaxCandleEndingPeriod = MaxCandleEndingPeriod;
+	type CancellationPenalty = CancellationPenalty;
+	type BidStep = BidStep;
+	type MinBidStep = MinBidStep;
+	type MaxBidStep = MaxBidStep;
+	type AntiSnipeBlocks = AntiSnipeBlocks;
+	type MaxAntiSnipeBlocks = MaxAntiSnipeBlocks;
+	type MinAuctionDuration = MinAuctionDuration;
+	type MaxBidHistory = MaxBidHistory;
+	type AuctionDepositBase = AuctionDepositBase;
+	type AuctionDepositPerByte = AuctionDepositPerByte;
+	type MaxNameLength = MaxNameLength;
+	type Commission = Commission;
+	type CommissionDestination = CommissionToFeeAccount;
+	type TokenCommissionAccount = TokenCommissionAccount;
 	type CurrencyBalance = Balance;
 	type WeightInfo = pallet_auction::weights::SubstrateWeight<Test>;
 }
@@ -76,6 +228,22 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		0
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = i128;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
 impl system::Config for Test {
 	type BaseCallFilter = ();
 	type BlockWeights = ();
@@ -1,23 +1,96 @@
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnFinalize, OnInitialize},
+};
 
 use super::*;
-use crate::{mock::*, Error};
-use pallet_nft::TokenData;
+use crate::{
+	mock::{Event, *},
+	Error,
+};
+use pallet_nft::{Royalty, TokenData};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
 
 pub type AuctionsModule = Module<Test>;
 pub type NFT = pallet_nft::Module<Test>;
 
 fn create_nft() {
+	create_nfts(1);
+}
+
+fn create_nfts(quantity: u32) {
 	assert_ok!(NFT::create_class(Origin::signed(100), "Class1".as_bytes().to_vec(), 0));
 	assert_ok!(NFT::mint(
 		Origin::signed(100),
 		0,
 		"Class1_mint1".as_bytes().to_vec(),
 		TokenData { locked: false },
-		1
+		quantity
 	));
 }
 
+fn english_auction(token_id: (u64, u64)) -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		name: "Aukce1".as_bytes().to_vec(),
+		last_bid: None,
+		start: 1,
+		end: 20,
+		owner: 100,
+		auction_type: AuctionType::English,
+		token_id,
+		minimal_bid: 50,
+		..Default::default()
+	}
+}
+
+fn candle_auction(token_id: (u64, u64)) -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		auction_type: AuctionType::Candle,
+		ending_period: Some(10),
+		..english_auction(token_id)
+	}
+}
+
+fn dutch_auction(token_id: (u64, u64), price_decay: PriceDecay<Balance, u64>) -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		auction_type: AuctionType::Dutch,
+		starting_price: 1_000,
+		floor_price: 400,
+		price_decay: Some(price_decay),
+		..english_auction(token_id)
+	}
+}
+
+fn fixed_swap(token_id: (u64, u64), price: Balance, end: u64) -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		auction_type: AuctionType::FixedSwap,
+		starting_price: price,
+		end,
+		..english_auction(token_id)
+	}
+}
+
+fn sealed_auction(token_id: (u64, u64)) -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		auction_type: AuctionType::SealedFirstPrice,
+		reveal_period: Some(5),
+		..english_auction(token_id)
+	}
+}
+
+fn commitment(value: Balance, salt: &[u8]) -> H256 {
+	BlakeTwo256::hash_of(&(value, salt.to_vec()))
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		AuctionsModule::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		AuctionsModule::on_initialize(System::block_number());
+	}
+}
+
 #[test]
 fn can_create_auction() {
 	new_test_ext().execute_with(|| {
@@ -30,6 +103,7 @@ fn can_create_auction() {
 			auction_type: AuctionType::English,
 			token_id: (0, 0),
 			minimal_bid: 50,
+			..Default::default()
 		};
 		assert_noop!(
 			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
@@ -40,12 +114,2677 @@ fn can_create_auction() {
 	});
 }
 
-// Build genesis storage according to the mock runtime.
-pub fn new_test_ext() -> sp_io::TestExternalities {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+#[test]
+fn bids_on_multiple_auctions_are_reserved_separately() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 1))
+		));
+		run_to_block(2);
 
-	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![(200, 500)],
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 1, 300));
+		assert_eq!(Balances::reserved_balance(200), 400);
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 100);
+		assert_eq!(AuctionsModule::reserved_bids(200, 1), 300);
+
+		// Being outbid in one auction releases only the funds backing that auction
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+		assert_eq!(Balances::reserved_balance(200), 300);
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 0);
+		assert_eq!(AuctionsModule::reserved_bids(200, 1), 300);
+		assert_eq!(Balances::reserved_balance(300), 200);
+	});
+}
+
+#[test]
+fn raising_own_bid_reserves_only_latest_amount() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(2);
+
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 200));
+		assert_eq!(Balances::reserved_balance(200), 200);
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 200);
+	});
+}
+
+#[test]
+fn bid_fails_without_enough_free_balance() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 1))
+		));
+		run_to_block(2);
+
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 6_000));
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(200), 1, 6_000),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 6_000);
+	});
+}
+
+#[test]
+fn conclusion_settles_from_reserved_funds() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		let mut later_auction = english_auction((0, 1));
+		later_auction.end = 40;
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), later_auction));
+		run_to_block(2);
+
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 1, 300));
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 0);
+		assert_eq!(AuctionsModule::reserved_bids(200, 1), 300);
+		assert_eq!(Balances::reserved_balance(200), 300);
+		assert_eq!(Balances::free_balance(200), 10_000 - 400);
+		assert_eq!(Balances::free_balance(100), 10_000 + 100);
+	});
+}
+
+#[test]
+fn anti_sniping_moves_end_time_index() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(15);
+
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+		assert_eq!(AuctionsModule::auction_end_time(25, 0), Some(()));
+		let extended = Event::pallet_auction(crate::Event::AuctionExtended(0, 25));
+		assert!(System::events().iter().any(|record| record.event == extended));
+		run_to_block(21);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		run_to_block(26);
+		assert!(NFT::is_owner(&200, (0, 0)));
+	});
+}
+
+#[test]
+fn candle_auction_requires_ending_period() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let mut auction_info = candle_auction((0, 0));
+		auction_info.ending_period = None;
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
+			Error::<Test>::InvalidEndingPeriod
+		);
+		auction_info.ending_period = Some(20);
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
+			Error::<Test>::InvalidEndingPeriod
+		);
+		// Longer than MaxCandleEndingPeriod
+		auction_info.end = 40;
+		auction_info.ending_period = Some(16);
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info),
+			Error::<Test>::InvalidEndingPeriod
+		);
+	});
+}
+
+/// Bids 100 before the ending period (10..20) and 200, 300 inside it at blocks 12 and 15
+fn setup_candle_bids() {
+	create_nft();
+	assert_ok!(AuctionsModule::create_auction(
+		Origin::signed(100),
+		candle_auction((0, 0))
+	));
+	run_to_block(5);
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+	run_to_block(12);
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+	run_to_block(15);
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 300));
+}
+
+#[test]
+fn candle_auction_keeps_ending_period_bids_reserved() {
+	new_test_ext().execute_with(|| {
+		setup_candle_bids();
+		// No anti-sniping extension for candle auctions
+		assert_eq!(AuctionsModule::auctions(0).unwrap().end, 20);
+		assert_eq!(AuctionsModule::candle_snapshots(0, 9), Some((200, 100)));
+		assert_eq!(AuctionsModule::candle_snapshots(0, 12), Some((300, 200)));
+		assert_eq!(AuctionsModule::candle_snapshots(0, 15), Some((200, 300)));
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 300);
+		assert_eq!(AuctionsModule::reserved_bids(300, 0), 200);
+	});
+}
+
+#[test]
+fn candle_auction_closed_before_ending_period_bids() {
+	new_test_ext().execute_with(|| {
+		TestRandomness::set_seed(0);
+		setup_candle_bids();
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 100);
+		assert_eq!(Balances::free_balance(200), 10_000 - 100);
+		assert_eq!(Balances::free_balance(300), 10_000);
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(Balances::reserved_balance(300), 0);
+		assert_eq!(AuctionsModule::candle_snapshots(0, 9), None);
+	});
+}
+
+#[test]
+fn candle_auction_closed_retroactively() {
+	new_test_ext().execute_with(|| {
+		TestRandomness::set_seed(3);
+		setup_candle_bids();
+		run_to_block(21);
+
+		// The candle went out at block 13, before the last bid was placed
+		let closed = Event::pallet_auction(crate::Event::CandleAuctionClosed(0, 13));
+		assert!(System::events().iter().any(|record| record.event == closed));
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 200);
+		assert_eq!(Balances::free_balance(200), 10_000);
+		assert_eq!(Balances::free_balance(300), 10_000 - 200);
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(Balances::reserved_balance(300), 0);
+	});
+}
+
+#[test]
+fn candle_auction_closed_after_last_bid() {
+	new_test_ext().execute_with(|| {
+		TestRandomness::set_seed(7);
+		setup_candle_bids();
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 300);
+		assert_eq!(Balances::free_balance(200), 10_000 - 300);
+		assert_eq!(Balances::free_balance(300), 10_000);
+		assert_eq!(Balances::reserved_balance(200), 0);
+	});
+}
+
+#[test]
+fn candle_auction_releases_bids_replaced_in_the_same_block() {
+	new_test_ext().execute_with(|| {
+		TestRandomness::set_seed(7);
+		setup_candle_bids();
+		// 200 keeps only the bid of its snapshot before the ending period backed
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(400), 0, 400));
+		assert_eq!(AuctionsModule::candle_snapshots(0, 15), Some((400, 400)));
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 100);
+		assert_eq!(Balances::reserved_balance(200), 100);
+
+		// 500 has no other snapshot and gets all its funds back
+		run_to_block(16);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(500), 0, 500));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(400), 0, 600));
+		assert_eq!(AuctionsModule::candle_snapshots(0, 16), Some((400, 600)));
+		assert_eq!(AuctionsModule::candle_bidders(0, 400), Some((16, Some(400))));
+		assert_eq!(AuctionsModule::reserved_bids(500, 0), 0);
+		assert_eq!(AuctionsModule::candle_bidders(0, 500), None);
+		assert_eq!(AuctionsModule::auctions_by_bidder(500, 0), None);
+		assert_eq!(Balances::reserved_balance(500), 0);
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&400, (0, 0)));
+		assert_eq!(Balances::free_balance(400), 10_000 - 600);
+		for bidder in [200, 300, 400, 500].iter() {
+			assert_eq!(Balances::reserved_balance(bidder), 0);
+			assert_eq!(AuctionsModule::auctions_by_bidder(bidder, 0), None);
+			assert_eq!(AuctionsModule::candle_bidders(0, bidder), None);
+		}
+	});
+}
+
+#[test]
+fn dutch_auction_requires_price_configuration() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let mut auction_info = dutch_auction((0, 0), PriceDecay::Linear(50));
+		auction_info.price_decay = None;
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+		auction_info.price_decay = Some(PriceDecay::Stepped { amount: 50, blocks: 0 });
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+		auction_info.price_decay = Some(PriceDecay::Linear(50));
+		auction_info.floor_price = 1_000;
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+	});
+}
+
+#[test]
+fn dutch_price_decays_linearly() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			dutch_auction((0, 0), PriceDecay::Linear(50))
+		));
+		// Price hits the floor of 400 after 12 blocks
+		assert_eq!(AuctionsModule::auctions(0).unwrap().end, 13);
+		assert_eq!(AuctionsModule::current_price(0), Some(1_000));
+		run_to_block(5);
+		assert_eq!(AuctionsModule::current_price(0), Some(800));
+		run_to_block(12);
+		assert_eq!(AuctionsModule::current_price(0), Some(450));
+	});
+}
+
+#[test]
+fn dutch_price_decays_in_steps() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			dutch_auction((0, 0), PriceDecay::Stepped { amount: 100, blocks: 3 })
+		));
+		run_to_block(6);
+		assert_eq!(AuctionsModule::current_price(0), Some(900));
+		run_to_block(7);
+		assert_eq!(AuctionsModule::current_price(0), Some(800));
+		// Never below the floor
+		let auction = AuctionsModule::auctions(0).unwrap();
+		assert_eq!(AuctionsModule::dutch_price(&auction, 100), Some(400));
+	});
+}
+
+#[test]
+fn can_buy_dutch_auction_at_current_price() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			dutch_auction((0, 0), PriceDecay::Linear(50))
+		));
+		assert_noop!(
+			AuctionsModule::buy(Origin::signed(200), 0),
+			Error::<Test>::AuctionNotStarted
+		);
+		run_to_block(5);
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(200), 0, 900),
+			Error::<Test>::BidNotAccepted
+		);
+		assert_ok!(AuctionsModule::buy(Origin::signed(200), 0));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionSold(0, 200, 800, 0))
+		);
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(Balances::free_balance(100), 10_000 + 800);
+		assert_eq!(Balances::free_balance(200), 10_000 - 800);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auction_end_time(13, 0), None);
+	});
+}
+
+#[test]
+fn dutch_auction_cancelled_at_floor() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			dutch_auction((0, 0), PriceDecay::Linear(50))
+		));
+		run_to_block(12);
+		assert!(AuctionsModule::auctions(0).is_some());
+		run_to_block(13);
+		assert_noop!(
+			AuctionsModule::buy(Origin::signed(200), 0),
+			Error::<Test>::AuctionNotExist
+		);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		let cancelled = Event::pallet_auction(crate::Event::AuctionConcludedWithoutSale(0));
+		assert!(System::events().iter().any(|record| record.event == cancelled));
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, Event::pallet_auction(crate::Event::AuctionConcluded(..)))));
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+	});
+}
+
+#[test]
+fn english_auction_cannot_be_bought() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_noop!(AuctionsModule::buy(Origin::signed(200), 0), Error::<Test>::NotForSale);
+	});
+}
+
+/// Four bids: 200 bids 100, 300 bids 200, 200 bids 300 and 400 wins with 400
+fn setup_top_up_bids(beneficiary: Option<u64>) {
+	create_nft();
+	let auction_info = AuctionInfo {
+		auction_type: AuctionType::TopUp,
+		beneficiary,
+		..english_auction((0, 0))
+	};
+	assert_ok!(AuctionsModule::create_auction(Origin::signed(100), auction_info));
+	run_to_block(2);
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 300));
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(400), 0, 400));
+}
+
+#[test]
+fn top_up_auction_keeps_every_bid() {
+	new_test_ext().execute_with(|| {
+		setup_top_up_bids(None);
+		assert_eq!(
+			AuctionsModule::top_up_bids(0),
+			vec![(200, 100), (300, 200), (200, 300), (400, 400)]
+		);
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 400);
+		assert_eq!(AuctionsModule::reserved_bids(300, 0), 200);
+		assert_eq!(AuctionsModule::reserved_bids(400, 0), 400);
+	});
+}
+
+#[test]
+fn top_up_auction_limits_number_of_bids() {
+	new_test_ext().execute_with(|| {
+		setup_top_up_bids(None);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(500), 0, 500));
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(200), 0, 600),
+			Error::<Test>::TooManyTopUpBids
+		);
+		assert_eq!(AuctionsModule::top_up_bids(0).len(), 5);
+	});
+}
+
+#[test]
+fn top_up_auction_charges_fees_to_outbid_participants() {
+	new_test_ext().execute_with(|| {
+		setup_top_up_bids(None);
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&400, (0, 0)));
+		let fee_paid = Event::pallet_auction(crate::Event::TopUpFeePaid(0, 300, 100));
+		assert!(System::events().iter().any(|record| record.event == fee_paid));
+		assert_eq!(Balances::free_balance(100), 10_000 + 400 + 100 + 100);
+		assert_eq!(Balances::free_balance(200), 10_000 - 100);
+		assert_eq!(Balances::free_balance(300), 10_000 - 100);
+		assert_eq!(Balances::free_balance(400), 10_000 - 400);
+		for bidder in &[200, 300, 400] {
+			assert_eq!(Balances::reserved_balance(bidder), 0);
+			assert_eq!(AuctionsModule::reserved_bids(bidder, 0), 0);
+		}
+		assert!(AuctionsModule::top_up_bids(0).is_empty());
+	});
+}
+
+#[test]
+fn top_up_fees_go_to_beneficiary() {
+	new_test_ext().execute_with(|| {
+		setup_top_up_bids(Some(500));
+		run_to_block(21);
+
+		assert_eq!(Balances::free_balance(100), 10_000 + 400);
+		assert_eq!(Balances::free_balance(500), 10_000 + 200);
+	});
+}
+
+#[test]
+fn fixed_swap_requires_price() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), fixed_swap((0, 0), 0, 0)),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+	});
+}
+
+#[test]
+fn can_buy_fixed_swap_listing() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			fixed_swap((0, 0), 500, 0)
+		));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(true));
+		assert_noop!(
+			NFT::transfer(Origin::signed(100), 300, (0, 0)),
+			pallet_nft::Error::<Test>::TokenLocked
+		);
+		// Listing without an end never expires
+		run_to_block(100);
+		assert_eq!(AuctionsModule::current_price(0), Some(500));
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(200), 0, 500),
+			Error::<Test>::BidNotAccepted
+		);
+		assert_ok!(AuctionsModule::buy(Origin::signed(200), 0));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionSold(0, 200, 500, 0))
+		);
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(Balances::free_balance(100), 10_000 + 500);
+		assert_eq!(Balances::free_balance(200), 10_000 - 500);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_noop!(
+			AuctionsModule::buy(Origin::signed(300), 0),
+			Error::<Test>::AuctionNotExist
+		);
+	});
+}
+
+#[test]
+fn fixed_swap_listing_expires() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			fixed_swap((0, 0), 500, 20)
+		));
+		run_to_block(21);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+	});
+}
+
+#[test]
+fn fixed_swap_listing_can_be_withdrawn() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			fixed_swap((0, 0), 500, 20)
+		));
+		run_to_block(5);
+		assert_ok!(AuctionsModule::delete_auction(Origin::signed(100), 0));
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+	});
+}
+
+#[test]
+fn sealed_auction_requires_reveal_period() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let mut auction_info = sealed_auction((0, 0));
+		auction_info.reveal_period = None;
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
+			Error::<Test>::InvalidRevealPeriod
+		);
+		auction_info.reveal_period = Some(19);
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info),
+			Error::<Test>::InvalidRevealPeriod
+		);
+	});
+}
+
+#[test]
+fn sealed_bids_are_committed_and_revealed() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			sealed_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(200), 0, 300),
+			Error::<Test>::BidNotAccepted
+		);
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(200),
+			0,
+			commitment(300, b"alice")
+		));
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(300),
+			0,
+			commitment(250, b"bob")
+		));
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(400),
+			0,
+			commitment(999, b"eve")
+		));
+		assert_eq!(Balances::reserved_balance(200), 10);
+		assert_noop!(
+			AuctionsModule::reveal_bid(Origin::signed(200), 0, 300, b"alice".to_vec()),
+			Error::<Test>::NotInRevealPhase
+		);
+
+		run_to_block(15);
+		assert_noop!(
+			AuctionsModule::commit_bid(Origin::signed(500), 0, commitment(400, b"late")),
+			Error::<Test>::NotInCommitPhase
+		);
+		assert_noop!(
+			AuctionsModule::reveal_bid(Origin::signed(200), 0, 300, b"bob".to_vec()),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(
+			AuctionsModule::reveal_bid(Origin::signed(500), 0, 300, b"alice".to_vec()),
+			Error::<Test>::BidNotCommitted
+		);
+		assert_ok!(AuctionsModule::reveal_bid(
+			Origin::signed(200),
+			0,
+			300,
+			b"alice".to_vec()
+		));
+		assert_ok!(AuctionsModule::reveal_bid(Origin::signed(300), 0, 250, b"bob".to_vec()));
+		assert_noop!(
+			AuctionsModule::reveal_bid(Origin::signed(300), 0, 250, b"bob".to_vec()),
+			Error::<Test>::BidAlreadyRevealed
+		);
+		assert_eq!(Balances::reserved_balance(200), 310);
+		assert_eq!(AuctionsModule::reserved_bids(300, 0), 260);
+	});
+}
+
+#[test]
+fn sealed_auction_settles_highest_reveal_and_slashes_unrevealed() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			sealed_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(200),
+			0,
+			commitment(300, b"alice")
+		));
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(300),
+			0,
+			commitment(250, b"bob")
+		));
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(400),
+			0,
+			commitment(999, b"eve")
+		));
+		run_to_block(15);
+		assert_ok!(AuctionsModule::reveal_bid(
+			Origin::signed(200),
+			0,
+			300,
+			b"alice".to_vec()
+		));
+		assert_ok!(AuctionsModule::reveal_bid(Origin::signed(300), 0, 250, b"bob".to_vec()));
+		run_to_block(21);
+
+		let won = Event::pallet_auction(crate::Event::SealedAuctionWon(0, 200, 300));
+		let slashed = Event::pallet_auction(crate::Event::SealedBidDepositSlashed(0, 400, 10));
+		assert!(System::events().iter().any(|record| record.event == won));
+		assert!(System::events().iter().any(|record| record.event == slashed));
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 300);
+		assert_eq!(Balances::free_balance(200), 10_000 - 300);
+		assert_eq!(Balances::free_balance(300), 10_000);
+		assert_eq!(Balances::free_balance(400), 10_000 - 10);
+		for bidder in &[200, 300, 400] {
+			assert_eq!(Balances::reserved_balance(bidder), 0);
+			assert_eq!(AuctionsModule::sealed_bids(0, bidder), None);
+		}
+	});
+}
+
+#[test]
+fn sealed_auction_limits_number_of_bidders() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			sealed_auction((0, 0))
+		));
+		run_to_block(2);
+		for bidder in &[200, 300, 400] {
+			assert_ok!(AuctionsModule::commit_bid(
+				Origin::signed(*bidder),
+				0,
+				commitment(300, b"salt")
+			));
+		}
+		assert_noop!(
+			AuctionsModule::commit_bid(Origin::signed(500), 0, commitment(400, b"salt")),
+			Error::<Test>::TooManySealedBids
+		);
+		// Committing again replaces the bid and takes no extra place
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(200),
+			0,
+			commitment(350, b"salt")
+		));
+		assert_eq!(AuctionsModule::sealed_bid_count(0), 3);
+		run_to_block(21);
+		assert_eq!(AuctionsModule::sealed_bid_count(0), 0);
+	});
+}
+
+#[test]
+fn sealed_auction_tie_goes_to_earlier_reveal() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			sealed_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(200),
+			0,
+			commitment(300, b"alice")
+		));
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(300),
+			0,
+			commitment(300, b"bob")
+		));
+		run_to_block(15);
+		assert_ok!(AuctionsModule::reveal_bid(Origin::signed(300), 0, 300, b"bob".to_vec()));
+		run_to_block(16);
+		assert_ok!(AuctionsModule::reveal_bid(
+			Origin::signed(200),
+			0,
+			300,
+			b"alice".to_vec()
+		));
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert_eq!(Balances::free_balance(200), 10_000);
+		assert_eq!(Balances::free_balance(300), 10_000 - 300);
+	});
+}
+
+fn vickrey_auction(token_id: (u64, u64)) -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		auction_type: AuctionType::Vickrey,
+		..sealed_auction(token_id)
+	}
+}
+
+/// Commits and reveals `bids` of `(bidder, amount)` in auction 0 and runs past its end
+fn reveal_and_settle(bids: &[(u64, Balance)]) {
+	run_to_block(2);
+	for (bidder, amount) in bids {
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(*bidder),
+			0,
+			commitment(*amount, b"salt")
+		));
+	}
+	run_to_block(15);
+	for (bidder, amount) in bids {
+		assert_ok!(AuctionsModule::reveal_bid(
+			Origin::signed(*bidder),
+			0,
+			*amount,
+			b"salt".to_vec()
+		));
+	}
+	run_to_block(21);
+}
+
+#[test]
+fn vickrey_winner_pays_second_highest_bid() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			vickrey_auction((0, 0))
+		));
+		reveal_and_settle(&[(200, 300), (300, 250), (400, 100)]);
+
+		let won = Event::pallet_auction(crate::Event::SealedAuctionWon(0, 200, 250));
+		assert!(System::events().iter().any(|record| record.event == won));
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 250);
+		assert_eq!(Balances::free_balance(200), 10_000 - 250);
+		assert_eq!(Balances::free_balance(300), 10_000);
+		assert_eq!(Balances::free_balance(400), 10_000);
+		for bidder in &[200, 300, 400] {
+			assert_eq!(Balances::reserved_balance(bidder), 0);
+		}
+	});
+}
+
+#[test]
+fn vickrey_single_bidder_pays_minimal_bid() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			vickrey_auction((0, 0))
+		));
+		reveal_and_settle(&[(200, 300)]);
+
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 50);
+		assert_eq!(Balances::free_balance(200), 10_000 - 50);
+		assert_eq!(Balances::reserved_balance(200), 0);
+	});
+}
+
+#[test]
+fn vickrey_winner_pays_at_least_the_reserve_price() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				reserve_price: Some(200),
+				..vickrey_auction((0, 0))
+			}
+		));
+		reveal_and_settle(&[(200, 300), (300, 100)]);
+
+		let won = Event::pallet_auction(crate::Event::SealedAuctionWon(0, 200, 200));
+		assert!(System::events().iter().any(|record| record.event == won));
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 200);
+		assert_eq!(Balances::free_balance(200), 10_000 - 200);
+		assert_eq!(Balances::free_balance(300), 10_000);
+	});
+}
+
+#[test]
+fn vickrey_bid_below_reserve_price_does_not_win() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				reserve_price: Some(200),
+				..vickrey_auction((0, 0))
+			}
+		));
+		reveal_and_settle(&[(200, 150)]);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcludedWithoutSale(0))
+		);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(Balances::free_balance(200), 10_000);
+		assert_eq!(Balances::reserved_balance(200), 0);
+	});
+}
+
+#[test]
+fn vickrey_tie_pays_tied_amount() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			vickrey_auction((0, 0))
+		));
+		reveal_and_settle(&[(300, 300), (200, 300), (400, 100)]);
+
+		// Both revealed in the same block, so the tie goes to either of them at the tied price
+		let winner = if NFT::is_owner(&200, (0, 0)) { 200 } else { 300 };
+		assert!(NFT::is_owner(&winner, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 300);
+		assert_eq!(Balances::free_balance(winner), 10_000 - 300);
+		assert_eq!(Balances::free_balance(200) + Balances::free_balance(300), 20_000 - 300);
+	});
+}
+
+#[test]
+fn vickrey_tie_goes_to_earlier_reveal() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			vickrey_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(200),
+			0,
+			commitment(300, b"alice")
+		));
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(300),
+			0,
+			commitment(300, b"bob")
+		));
+		run_to_block(15);
+		assert_ok!(AuctionsModule::reveal_bid(Origin::signed(300), 0, 300, b"bob".to_vec()));
+		run_to_block(16);
+		assert_ok!(AuctionsModule::reveal_bid(
+			Origin::signed(200),
+			0,
+			300,
+			b"alice".to_vec()
+		));
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert_eq!(Balances::free_balance(300), 10_000 - 300);
+		assert_eq!(Balances::free_balance(200), 10_000);
+	});
+}
+
+fn bundle_auction() -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		auction_type: AuctionType::Combinatorial,
+		bundle: vec![(0, 1), (0, 2)],
+		..english_auction((0, 0))
+	}
+}
+
+#[test]
+fn bundle_auction_validates_tokens() {
+	new_test_ext().execute_with(|| {
+		create_nfts(5);
+		let mut auction_info = bundle_auction();
+		auction_info.bundle = vec![(0, 1), (0, 1)];
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
+			Error::<Test>::InvalidBundle
+		);
+		auction_info.bundle = vec![(0, 1), (0, 2), (0, 3), (0, 4)];
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info.clone()),
+			Error::<Test>::InvalidBundle
+		);
+		auction_info.bundle = vec![(0, 1), (0, 7)];
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info),
+			Error::<Test>::NotATokenOwner
+		);
+		let mut english = english_auction((0, 0));
+		english.bundle = vec![(0, 1)];
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), english),
+			Error::<Test>::InvalidBundle
+		);
+
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), bundle_auction()));
+		for token in 0..3 {
+			assert_eq!(NFT::is_locked((0, token)), Ok(true));
+		}
+	});
+}
+
+#[test]
+fn bundle_bids_are_validated_and_reserved() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), bundle_auction()));
+		run_to_block(2);
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(200), 0, 100),
+			Error::<Test>::BidNotAccepted
+		);
+		assert_noop!(
+			AuctionsModule::bid_bundle(Origin::signed(200), 0, 0, 100),
+			Error::<Test>::InvalidBundle
+		);
+		assert_noop!(
+			AuctionsModule::bid_bundle(Origin::signed(200), 0, 0b1000, 100),
+			Error::<Test>::InvalidBundle
+		);
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(200), 0, 0b011, 500));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(200), 0, 0b100, 100));
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 600);
+		// Bidding again on the same bundle raises the bid
+		assert_noop!(
+			AuctionsModule::bid_bundle(Origin::signed(200), 0, 0b011, 400),
+			Error::<Test>::InvalidBidPrice
+		);
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(200), 0, 0b011, 700));
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 800);
+		assert_eq!(AuctionsModule::bundle_bids(0).len(), 2);
+
+		for bidder in &[300, 400, 500] {
+			assert_ok!(AuctionsModule::bid_bundle(Origin::signed(*bidder), 0, 0b001, 100));
+		}
+		assert_noop!(
+			AuctionsModule::bid_bundle(Origin::signed(300), 0, 0b010, 100),
+			Error::<Test>::TooManyBundleBids
+		);
+	});
+}
+
+#[test]
+fn bundle_auction_maximizes_revenue() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), bundle_auction()));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(200), 0, 0b011, 500));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(300), 0, 0b001, 300));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(400), 0, 0b010, 300));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(500), 0, 0b100, 100));
+		run_to_block(21);
+
+		// Selling the items separately (700) beats the pair bid of 500 plus the third item
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert!(NFT::is_owner(&400, (0, 1)));
+		assert!(NFT::is_owner(&500, (0, 2)));
+		let won = Event::pallet_auction(crate::Event::BundleWon(0, 400, 0b010, 300));
+		assert!(System::events().iter().any(|record| record.event == won));
+		assert_eq!(Balances::free_balance(100), 10_000 + 700);
+		assert_eq!(Balances::free_balance(200), 10_000);
+		assert_eq!(Balances::free_balance(300), 10_000 - 300);
+		for bidder in &[200, 300, 400, 500] {
+			assert_eq!(Balances::reserved_balance(bidder), 0);
+		}
+		for token in 0..3 {
+			assert_eq!(NFT::is_locked((0, token)), Ok(false));
+		}
+		assert!(AuctionsModule::bundle_bids(0).is_empty());
+	});
+}
+
+#[test]
+fn bundle_auction_without_bids_concludes_without_sale() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), bundle_auction()));
+		run_to_block(21);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcludedWithoutSale(0))
+		);
+		for token_id in 0..3 {
+			assert!(NFT::is_owner(&100, (0, token_id)));
+			assert_eq!(NFT::is_locked((0, token_id)), Ok(false));
+		}
+	});
+}
+
+#[test]
+fn bundle_auction_awards_pair_when_worth_more() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), bundle_auction()));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(200), 0, 0b011, 700));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(300), 0, 0b001, 300));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(400), 0, 0b110, 300));
+		run_to_block(21);
+
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert!(NFT::is_owner(&200, (0, 1)));
+		// Nobody bid on the last item alone, so it stays with the owner
+		assert!(NFT::is_owner(&100, (0, 2)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 700);
+		assert_eq!(Balances::free_balance(200), 10_000 - 700);
+		assert_eq!(Balances::free_balance(300), 10_000);
+		assert_eq!(Balances::free_balance(400), 10_000);
+	});
+}
+
+#[test]
+fn reserve_price_only_for_english_candle_and_vickrey_auctions() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let mut auction_info = dutch_auction((0, 0), PriceDecay::Linear(50));
+		auction_info.reserve_price = Some(500);
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+	});
+}
+
+#[test]
+fn auction_below_reserve_price_concludes_without_sale() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let auction_info = AuctionInfo {
+			reserve_price: Some(500),
+			..english_auction((0, 0))
+		};
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), auction_info));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 400));
+		run_to_block(21);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcludedWithoutSale(0))
+		);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(Balances::free_balance(100), 10_000);
+		assert_eq!(Balances::free_balance(300), 10_000);
+		assert_eq!(Balances::reserved_balance(300), 0);
+		assert_eq!(AuctionsModule::reserved_bids(300, 0), 0);
+		assert_eq!(AuctionsModule::auctions(0), None);
+	});
+}
+
+#[test]
+fn auction_without_bids_and_reserve_price_concludes_without_sale() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let auction_info = AuctionInfo {
+			reserve_price: Some(500),
+			..english_auction((0, 0))
+		};
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), auction_info));
+		run_to_block(21);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcludedWithoutSale(0))
+		);
+		assert!(NFT::is_owner(&100, (0, 0)));
+	});
+}
+
+#[test]
+fn hidden_reserve_price_applies_once_revealed() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::commit_reserve(
+			Origin::signed(100),
+			0,
+			commitment(500, b"salt")
+		));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::ReserveCommitted(0))
+		);
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 400));
+		assert_eq!(AuctionsModule::auctions(0).unwrap().reserve_price, None);
+
+		assert_noop!(
+			AuctionsModule::reveal_reserve(Origin::signed(100), 0, 500, b"other".to_vec()),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(
+			AuctionsModule::reveal_reserve(Origin::signed(300), 0, 500, b"salt".to_vec()),
+			Error::<Test>::NotAuctionOwner
+		);
+		assert_ok!(AuctionsModule::reveal_reserve(
+			Origin::signed(100),
+			0,
+			500,
+			b"salt".to_vec()
+		));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::ReserveRevealed(0, 500))
+		);
+		assert_eq!(AuctionsModule::auctions(0).unwrap().reserve_price, Some(500));
+		assert_eq!(AuctionsModule::reserve_commitments(0), None);
+		run_to_block(21);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcludedWithoutSale(0))
+		);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(Balances::free_balance(300), 10_000);
+	});
+}
+
+#[test]
+fn unrevealed_hidden_reserve_price_does_not_apply() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::commit_reserve(
+			Origin::signed(100),
+			0,
+			commitment(500, b"salt")
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 400));
+		run_to_block(20);
+		assert_noop!(
+			AuctionsModule::reveal_reserve(Origin::signed(100), 0, 500, b"salt".to_vec()),
+			Error::<Test>::AuctionNotExist
+		);
+
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert_eq!(AuctionsModule::reserve_commitments(0), None);
+	});
+}
+
+#[test]
+fn only_owner_commits_hidden_reserve_price_before_start() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				reserve_price: Some(500),
+				..english_auction((0, 1))
+			}
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			dutch_auction((0, 2), PriceDecay::Linear(50))
+		));
+		assert_noop!(
+			AuctionsModule::commit_reserve(Origin::signed(200), 0, commitment(500, b"salt")),
+			Error::<Test>::NotAuctionOwner
+		);
+		assert_noop!(
+			AuctionsModule::commit_reserve(Origin::signed(100), 1, commitment(500, b"salt")),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+		assert_noop!(
+			AuctionsModule::commit_reserve(Origin::signed(100), 2, commitment(500, b"salt")),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+		assert_noop!(
+			AuctionsModule::reveal_reserve(Origin::signed(100), 0, 500, b"salt".to_vec()),
+			Error::<Test>::ReserveNotCommitted
+		);
+		run_to_block(2);
+		assert_noop!(
+			AuctionsModule::commit_reserve(Origin::signed(100), 0, commitment(500, b"salt")),
+			Error::<Test>::AuctionAlreadyStarted
+		);
+	});
+}
+
+#[test]
+fn auction_meeting_reserve_price_is_sold() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let auction_info = AuctionInfo {
+			reserve_price: Some(500),
+			..english_auction((0, 0))
+		};
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), auction_info));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 500));
+		run_to_block(21);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcluded(0, 500, 0))
+		);
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 500);
+	});
+}
+
+fn buy_now_auction() -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		buy_now_price: Some(1_000),
+		..english_auction((0, 0))
+	}
+}
+
+#[test]
+fn buy_now_price_is_validated() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let mut auction_info = dutch_auction((0, 0), PriceDecay::Linear(50));
+		auction_info.buy_now_price = Some(1_000);
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+		let auction_info = AuctionInfo {
+			reserve_price: Some(2_000),
+			..buy_now_auction()
+		};
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(100), auction_info),
+			Error::<Test>::InvalidPriceConfiguration
+		);
+	});
+}
+
+#[test]
+fn buy_now_ends_english_auction() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), buy_now_auction()));
+		run_to_block(15);
+		// Anti-sniping moves the end to block 25
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+		assert_eq!(AuctionsModule::current_price(0), Some(1_000));
+		assert_ok!(AuctionsModule::buy(Origin::signed(200), 0));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionBoughtNow(0, 200, 1_000, 0))
+		);
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(Balances::free_balance(100), 10_000 + 1_000);
+		assert_eq!(Balances::free_balance(200), 10_000 - 1_000);
+		assert_eq!(Balances::free_balance(300), 10_000);
+		assert_eq!(AuctionsModule::reserved_bids(300, 0), 0);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auction_end_time(25, 0), None);
+	});
+}
+
+#[test]
+fn buy_now_unavailable_once_bids_reach_it() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), buy_now_auction()));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 1_000));
+		assert_eq!(AuctionsModule::current_price(0), None);
+		assert_noop!(AuctionsModule::buy(Origin::signed(200), 0), Error::<Test>::NotForSale);
+	});
+}
+
+/// Bid of 200 which is no longer backed by reserved funds when the auction ends
+fn setup_failed_settlement() {
+	create_nft();
+	assert_ok!(AuctionsModule::create_auction(
+		Origin::signed(100),
+		english_auction((0, 0))
+	));
+	run_to_block(2);
+	assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+	Balances::unreserve(&200, 100);
+	run_to_block(21);
+}
+
+#[test]
+fn failed_settlement_is_rolled_back_and_recorded() {
+	new_test_ext().execute_with(|| {
+		setup_failed_settlement();
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::SettlementFailed(
+				0,
+				Error::<Test>::InsufficientReservedFunds.into()
+			))
+		);
+		assert_eq!(
+			AuctionsModule::failed_settlements(0),
+			Some(Error::<Test>::InsufficientReservedFunds.into())
+		);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(true));
+		assert_eq!(Balances::free_balance(100), 10_000);
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 100);
+		assert!(AuctionsModule::auctions(0).is_some());
+	});
+}
+
+#[test]
+fn retry_settlement_completes_failed_settlement() {
+	new_test_ext().execute_with(|| {
+		setup_failed_settlement();
+		assert_noop!(
+			AuctionsModule::retry_settlement(Origin::signed(300), 0),
+			Error::<Test>::InsufficientReservedFunds
+		);
+
+		assert_ok!(Balances::reserve(&200, 100));
+		assert_ok!(AuctionsModule::retry_settlement(Origin::signed(300), 0));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcluded(0, 100, 0))
+		);
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(Balances::free_balance(100), 10_000 + 100);
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(AuctionsModule::failed_settlements(0), None);
+		assert_eq!(AuctionsModule::auctions(0), None);
+	});
+}
+
+#[test]
+fn root_can_abort_failed_settlement() {
+	new_test_ext().execute_with(|| {
+		setup_failed_settlement();
+		assert_noop!(
+			AuctionsModule::abort_settlement(Origin::signed(100), 0),
+			sp_runtime::traits::BadOrigin
+		);
+
+		assert_ok!(AuctionsModule::abort_settlement(Origin::root(), 0));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::SettlementAborted(0))
+		);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(AuctionsModule::reserved_bids(200, 0), 0);
+		assert_eq!(AuctionsModule::auctions_by_bidder(200, 0), None);
+		assert_eq!(AuctionsModule::failed_settlements(0), None);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_noop!(
+			AuctionsModule::abort_settlement(Origin::root(), 0),
+			Error::<Test>::SettlementNotFailed
+		);
+	});
+}
+
+#[test]
+fn retry_settlement_requires_failed_settlement() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_noop!(
+			AuctionsModule::retry_settlement(Origin::signed(300), 0),
+			Error::<Test>::SettlementNotFailed
+		);
+	});
+}
+
+#[test]
+fn conclusions_over_block_limit_are_queued() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		for token_id in 0..3 {
+			assert_ok!(AuctionsModule::create_auction(
+				Origin::signed(100),
+				english_auction((0, token_id))
+			));
+		}
+		run_to_block(20);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auctions(1), None);
+		assert!(AuctionsModule::auctions(2).is_some());
+		assert_eq!(AuctionsModule::conclusion_queue_bounds(), (0, 1));
+		assert_eq!(AuctionsModule::queued_conclusion(0), Some(2));
+		assert_eq!(AuctionsModule::auction_end_time(20, 2), None);
+
+		run_to_block(21);
+		assert_eq!(AuctionsModule::auctions(2), None);
+		assert_eq!(NFT::is_locked((0, 2)), Ok(false));
+		assert_eq!(AuctionsModule::conclusion_queue_bounds(), (0, 0));
+		assert_eq!(AuctionsModule::queued_conclusion(0), None);
+	});
+}
+
+#[test]
+fn queued_conclusions_come_before_new_ones() {
+	new_test_ext().execute_with(|| {
+		create_nfts(5);
+		for token_id in 0..4 {
+			assert_ok!(AuctionsModule::create_auction(
+				Origin::signed(100),
+				english_auction((0, token_id))
+			));
+		}
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				end: 21,
+				..english_auction((0, 4))
+			}
+		));
+		run_to_block(20);
+		assert_eq!(AuctionsModule::conclusion_queue_bounds(), (0, 2));
+
+		run_to_block(21);
+		assert_eq!(AuctionsModule::auctions(2), None);
+		assert_eq!(AuctionsModule::auctions(3), None);
+		assert!(AuctionsModule::auctions(4).is_some());
+		assert_eq!(AuctionsModule::conclusion_queue_bounds(), (2, 3));
+		assert_eq!(AuctionsModule::queued_conclusion(2), Some(4));
+
+		run_to_block(22);
+		assert_eq!(AuctionsModule::auctions(4), None);
+		assert_eq!(AuctionsModule::conclusion_queue_bounds(), (0, 0));
+	});
+}
+
+#[test]
+fn conclusion_weight_grows_with_concluded_auctions() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(19);
+		let idle = AuctionsModule::on_initialize(19);
+		assert!(AuctionsModule::on_initialize(20) > idle);
+		assert_eq!(AuctionsModule::auctions(0), None);
+	});
+}
+
+#[test]
+fn settlement_extrinsics_charge_only_the_used_weight() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		let declared = <Test as Config>::WeightInfo::close_auction() + AuctionsModule::max_settlement_weight();
+		let info = AuctionsModule::close_auction(Origin::signed(100), 0).unwrap();
+		assert!(info.actual_weight.unwrap() < declared);
+	});
+}
+
+#[test]
+fn cannot_create_auction_for_someone_else() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_noop!(
+			AuctionsModule::create_auction(Origin::signed(200), english_auction((0, 0))),
+			Error::<Test>::NotAuctionOwner
+		);
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+	});
+}
+
+#[test]
+fn only_owner_can_delete_auction() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_noop!(
+			AuctionsModule::delete_auction(Origin::signed(200), 0),
+			Error::<Test>::NotAuctionOwner
+		);
+		assert_noop!(
+			AuctionsModule::delete_auction(Origin::signed(200), 1),
+			Error::<Test>::AuctionNotExist
+		);
+		assert!(AuctionsModule::auctions(0).is_some());
+	});
+}
+
+#[test]
+fn root_can_force_delete_auction() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_noop!(
+			AuctionsModule::force_delete_auction(Origin::signed(100), 0),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(AuctionsModule::force_delete_auction(Origin::root(), 0));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionRemoved(0))
+		);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+	});
+}
+
+#[test]
+fn delete_auction_charges_only_the_weight_of_its_tokens() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		let declared = <Test as Config>::WeightInfo::delete_auction(MaxBundleItems::get());
+		let info = AuctionsModule::delete_auction(Origin::signed(100), 0).unwrap();
+		assert_eq!(
+			info.actual_weight,
+			Some(<Test as Config>::WeightInfo::delete_auction(1))
+		);
+		assert!(info.actual_weight.unwrap() < declared);
+	});
+}
+
+#[test]
+fn owner_can_update_auction_before_start() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				start: 5,
+				..english_auction((0, 0))
+			}
+		));
+		let updated = AuctionInfo {
+			name: "Aukce2".as_bytes().to_vec(),
+			start: 3,
+			end: 30,
+			minimal_bid: 80,
+			auction_type: AuctionType::Candle,
+			ending_period: Some(10),
+			..english_auction((0, 0))
+		};
+		assert_ok!(AuctionsModule::update_auction(Origin::signed(100), 0, updated.clone()));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionUpdated(0))
+		);
+		assert_eq!(AuctionsModule::auctions(0), Some(updated));
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+		assert_eq!(AuctionsModule::auction_end_time(30, 0), Some(()));
+	});
+}
+
+#[test]
+fn update_auction_is_validated() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_noop!(
+			AuctionsModule::update_auction(Origin::signed(200), 0, english_auction((0, 0))),
+			Error::<Test>::NotAuctionOwner
+		);
+		assert_noop!(
+			AuctionsModule::update_auction(Origin::signed(100), 0, english_auction((0, 1))),
+			Error::<Test>::InvalidAuctionUpdate
+		);
+		assert_noop!(
+			AuctionsModule::update_auction(
+				Origin::signed(100),
+				0,
+				AuctionInfo {
+					end: 5,
+					..english_auction((0, 0))
+				}
+			),
+			Error::<Test>::InvalidTimeConfiguration
+		);
+		assert_noop!(
+			AuctionsModule::update_auction(
+				Origin::signed(100),
+				0,
+				AuctionInfo {
+					auction_type: AuctionType::Candle,
+					..english_auction((0, 0))
+				}
+			),
+			Error::<Test>::InvalidEndingPeriod
+		);
+	});
+}
+
+#[test]
+fn started_auction_can_only_be_extended_or_get_lower_reserve() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		let auction_info = AuctionInfo {
+			reserve_price: Some(500),
+			..english_auction((0, 0))
+		};
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			auction_info.clone()
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+
+		for rejected in vec![
+			AuctionInfo {
+				end: 19,
+				..auction_info.clone()
+			},
+			AuctionInfo {
+				reserve_price: Some(600),
+				..auction_info.clone()
+			},
+			AuctionInfo {
+				minimal_bid: 10,
+				..auction_info.clone()
+			},
+		] {
+			assert_noop!(
+				AuctionsModule::update_auction(Origin::signed(100), 0, rejected),
+				Error::<Test>::InvalidAuctionUpdate
+			);
+		}
+
+		assert_ok!(AuctionsModule::update_auction(
+			Origin::signed(100),
+			0,
+			AuctionInfo {
+				end: 40,
+				reserve_price: Some(100),
+				..auction_info
+			}
+		));
+		let extended = Event::pallet_auction(crate::Event::AuctionExtended(0, 40));
+		assert!(System::events().iter().any(|record| record.event == extended));
+		let auction = AuctionsModule::auctions(0).unwrap();
+		assert_eq!(auction.end, 40);
+		assert_eq!(auction.reserve_price, Some(100));
+		assert_eq!(auction.last_bid, Some((200, 100)));
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+		assert_eq!(AuctionsModule::auction_end_time(40, 0), Some(()));
+
+		run_to_block(40);
+		assert!(NFT::is_owner(&200, (0, 0)));
+	});
+}
+
+#[test]
+fn ended_auction_can_not_be_updated() {
+	new_test_ext().execute_with(|| {
+		setup_failed_settlement();
+		assert_noop!(
+			AuctionsModule::update_auction(
+				Origin::signed(100),
+				0,
+				AuctionInfo {
+					end: 40,
+					..english_auction((0, 0))
+				}
+			),
+			Error::<Test>::AuctionAlreadyConcluded
+		);
+		assert_eq!(AuctionsModule::auction_end_time(40, 0), None);
+	});
+}
+
+#[test]
+fn started_candle_auction_end_can_not_move() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			candle_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_noop!(
+			AuctionsModule::update_auction(
+				Origin::signed(100),
+				0,
+				AuctionInfo {
+					end: 30,
+					..candle_auction((0, 0))
+				}
+			),
+			Error::<Test>::InvalidAuctionUpdate
+		);
+	});
+}
+
+#[test]
+fn owner_can_accept_highest_bid_early() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				reserve_price: Some(500),
+				..english_auction((0, 0))
+			}
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_noop!(
+			AuctionsModule::close_auction(Origin::signed(200), 0),
+			Error::<Test>::NotAuctionOwner
+		);
+		assert_ok!(AuctionsModule::close_auction(Origin::signed(100), 0));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcluded(0, 100, 0))
+		);
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(Balances::free_balance(100), 10_000 + 100);
+		assert_eq!(Balances::free_balance(200), 10_000 - 100);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+	});
+}
+
+#[test]
+fn close_auction_requires_a_bid_on_english_or_top_up_auction() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			candle_auction((0, 1))
+		));
+		run_to_block(2);
+		assert_noop!(
+			AuctionsModule::close_auction(Origin::signed(100), 0),
+			Error::<Test>::NoBidToAccept
+		);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 1, 100));
+		assert_noop!(
+			AuctionsModule::close_auction(Origin::signed(100), 1),
+			Error::<Test>::CannotCloseAuction
+		);
+	});
+}
+
+#[test]
+fn ended_auction_can_not_be_closed() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		for token_id in 0..3 {
+			assert_ok!(AuctionsModule::create_auction(
+				Origin::signed(100),
+				AuctionInfo {
+					reserve_price: Some(500),
+					..english_auction((0, token_id))
+				}
+			));
+		}
+		run_to_block(2);
+		for id in 0..3 {
+			assert_ok!(AuctionsModule::bid_value(Origin::signed(200), id, 100));
+		}
+		run_to_block(20);
+		assert_eq!(AuctionsModule::queued_conclusion(0), Some(2));
+		assert_noop!(
+			AuctionsModule::close_auction(Origin::signed(100), 2),
+			Error::<Test>::AuctionAlreadyConcluded
+		);
+
+		run_to_block(21);
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionConcludedWithoutSale(2))
+		);
+		assert!(NFT::is_owner(&100, (0, 2)));
+		assert_eq!(Balances::free_balance(200), 10_000);
+	});
+}
+
+#[test]
+fn cancelled_auction_refunds_leader_with_penalty() {
+	new_test_ext().execute_with(|| {
+		CancellationPenalty::set(50);
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_eq!(Balances::reserved_balance(100), 50);
+		assert_eq!(AuctionsModule::seller_deposits(0), 50);
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+		assert_ok!(AuctionsModule::cancel_auction(Origin::signed(100), 0));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionCancelled(0, 50))
+		);
+		assert!(NFT::is_owner(&100, (0, 0)));
+		assert_eq!(NFT::is_locked((0, 0)), Ok(false));
+		assert_eq!(Balances::free_balance(100), 10_000 - 50);
+		assert_eq!(Balances::reserved_balance(100), 0);
+		assert_eq!(Balances::free_balance(200), 10_000);
+		assert_eq!(Balances::free_balance(300), 10_000 + 50);
+		assert_eq!(Balances::reserved_balance(300), 0);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+		assert_eq!(AuctionsModule::seller_deposits(0), 0);
+	});
+}
+
+#[test]
+fn cancel_auction_refunds_every_top_up_bid() {
+	new_test_ext().execute_with(|| {
+		setup_top_up_bids(None);
+		assert_ok!(AuctionsModule::cancel_auction(Origin::signed(100), 0));
+
+		for bidder in [200, 300, 400].iter() {
+			assert_eq!(Balances::free_balance(bidder), 10_000);
+			assert_eq!(AuctionsModule::reserved_bids(bidder, 0), 0);
+		}
+		assert!(AuctionsModule::top_up_bids(0).is_empty());
+	});
+}
+
+#[test]
+fn cancel_auction_only_while_running() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_noop!(
+			AuctionsModule::cancel_auction(Origin::signed(100), 0),
+			Error::<Test>::AuctionNotStarted
+		);
+		run_to_block(2);
+		assert_noop!(
+			AuctionsModule::cancel_auction(Origin::signed(200), 0),
+			Error::<Test>::NotAuctionOwner
+		);
+		assert_ok!(AuctionsModule::cancel_auction(Origin::signed(100), 0));
+	});
+}
+
+#[test]
+fn seller_deposit_is_returned_on_conclusion() {
+	new_test_ext().execute_with(|| {
+		CancellationPenalty::set(50);
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		run_to_block(20);
+
+		assert_eq!(Balances::reserved_balance(100), 0);
+		assert_eq!(Balances::free_balance(100), 10_000 + 100);
+		assert_eq!(AuctionsModule::seller_deposits(0), 0);
+	});
+}
+
+fn inactivity_auction(token_id: (u64, u64)) -> AuctionInfoOf<Test> {
+	AuctionInfo {
+		inactivity_window: Some(5),
+		..english_auction(token_id)
+	}
+}
+
+#[test]
+fn inactive_auction_ends_after_window() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			inactivity_auction((0, 0))
+		));
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), Some(()));
+		run_to_block(3);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), None);
+		assert_eq!(AuctionsModule::auction_end_time(8, 0), Some(()));
+		run_to_block(6);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+		assert_eq!(AuctionsModule::auction_end_time(8, 0), None);
+		assert_eq!(AuctionsModule::auction_end_time(11, 0), Some(()));
+		assert_eq!(AuctionsModule::auction_end_by_id(0), Some(11));
+
+		run_to_block(10);
+		assert!(AuctionsModule::auctions(0).is_some());
+		run_to_block(11);
+		assert_eq!(AuctionsModule::auctions(0), None);
+		assert_eq!(AuctionsModule::auction_end_by_id(0), None);
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 200);
+	});
+}
+
+#[test]
+fn inactivity_window_never_goes_past_end() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				inactivity_window: Some(15),
+				..english_auction((0, 0))
+			}
+		));
+		run_to_block(3);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_eq!(AuctionsModule::auction_end_time(18, 0), Some(()));
+		// Anti-sniping postpones the end, which still bounds the window
+		run_to_block(17);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+		assert_eq!(AuctionsModule::auctions(0).unwrap().end, 27);
+		assert_eq!(AuctionsModule::auction_end_time(18, 0), None);
+		assert_eq!(AuctionsModule::auction_end_time(27, 0), Some(()));
+	});
+}
+
+#[test]
+fn inactivity_window_only_for_english_auctions() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_noop!(
+			AuctionsModule::create_auction(
+				Origin::signed(100),
+				AuctionInfo {
+					inactivity_window: Some(5),
+					..candle_auction((0, 0))
+				}
+			),
+			Error::<Test>::InvalidInactivityWindow
+		);
+		assert_noop!(
+			AuctionsModule::create_auction(
+				Origin::signed(100),
+				AuctionInfo {
+					inactivity_window: Some(0),
+					..english_auction((0, 0))
+				}
+			),
+			Error::<Test>::InvalidInactivityWindow
+		);
+	});
+}
+
+#[test]
+fn auction_can_set_own_bid_step_and_increment() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				bid_step: Some(Permill::from_percent(25)),
+				..english_auction((0, 0))
+			}
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				min_bid_increment: Some(30),
+				..english_auction((0, 1))
+			}
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_eq!(AuctionsModule::auctions(0).unwrap().minimal_bid, 125);
+		// The absolute increment applies while it is above the percentage
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 1, 100));
+		assert_eq!(AuctionsModule::auctions(1).unwrap().minimal_bid, 130);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 1, 1_000));
+		assert_eq!(AuctionsModule::auctions(1).unwrap().minimal_bid, 1_100);
+	});
+}
+
+#[test]
+fn auction_can_set_own_anti_sniping_blocks() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				anti_snipe_blocks: Some(3),
+				..english_auction((0, 0))
+			}
+		));
+		run_to_block(15);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_eq!(AuctionsModule::auction_end_time(20, 0), Some(()));
+		run_to_block(18);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+		assert_eq!(AuctionsModule::auction_end_time(21, 0), Some(()));
+	});
+}
+
+#[test]
+fn bid_rules_are_bounded_by_runtime() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_noop!(
+			AuctionsModule::create_auction(
+				Origin::signed(100),
+				AuctionInfo {
+					bid_step: Some(Permill::from_percent(60)),
+					..english_auction((0, 0))
+				}
+			),
+			Error::<Test>::InvalidBidRules
+		);
+		assert_noop!(
+			AuctionsModule::create_auction(
+				Origin::signed(100),
+				AuctionInfo {
+					anti_snipe_blocks: Some(51),
+					..english_auction((0, 0))
+				}
+			),
+			Error::<Test>::InvalidBidRules
+		);
+	});
+}
+
+#[test]
+fn bids_have_to_increase_by_a_step_or_an_increment() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		for (bid_step, min_bid_increment) in vec![
+			(Some(Permill::zero()), None),
+			(Some(Permill::zero()), Some(0)),
+			(Some(Permill::from_perthousand(5)), None),
+		] {
+			assert_noop!(
+				AuctionsModule::create_auction(
+					Origin::signed(100),
+					AuctionInfo {
+						bid_step,
+						min_bid_increment,
+						..english_auction((0, 0))
+					}
+				),
+				Error::<Test>::InvalidBidRules
+			);
+		}
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				bid_step: Some(Permill::zero()),
+				min_bid_increment: Some(10),
+				..english_auction((0, 0))
+			}
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(300), 0, 105),
+			Error::<Test>::InvalidBidPrice
+		);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 110));
+	});
+}
+
+fn bid_record(bidder: u64, amount: Balance, block: u64) -> BidRecordOf<Test> {
+	BidRecord { bidder, amount, block }
+}
+
+#[test]
+fn bid_history_keeps_most_recent_bids() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		for (block, (bidder, amount)) in [(200, 100), (300, 200), (200, 300), (400, 400)].iter().enumerate() {
+			run_to_block(block as u64 + 2);
+			assert_ok!(AuctionsModule::bid_value(Origin::signed(*bidder), 0, *amount));
+		}
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(300), 0, 400),
+			Error::<Test>::InvalidBidPrice
+		);
+
+		assert_eq!(
+			AuctionsModule::bid_history(0),
+			vec![
+				bid_record(300, 200, 3),
+				bid_record(200, 300, 4),
+				bid_record(400, 400, 5)
+			]
+		);
+		assert_eq!(AuctionsModule::bid_history_bounds(0), (1, 3));
+		assert_eq!(AuctionsModule::bid_record(0, 0), Some(bid_record(400, 400, 5)));
+	});
+}
+
+#[test]
+fn bid_history_records_sealed_and_bundle_bids() {
+	new_test_ext().execute_with(|| {
+		create_nfts(4);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			sealed_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				auction_type: AuctionType::Combinatorial,
+				bundle: vec![(0, 2), (0, 3)],
+				..english_auction((0, 1))
+			}
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::commit_bid(
+			Origin::signed(200),
+			0,
+			commitment(300, b"alice")
+		));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(300), 1, 0b011, 150));
+		assert!(AuctionsModule::bid_history(0).is_empty());
+		assert_eq!(AuctionsModule::bid_history(1), vec![bid_record(300, 150, 2)]);
+
+		run_to_block(15);
+		assert_ok!(AuctionsModule::reveal_bid(
+			Origin::signed(200),
+			0,
+			300,
+			b"alice".to_vec()
+		));
+		assert_eq!(AuctionsModule::bid_history(0), vec![bid_record(200, 300, 15)]);
+	});
+}
+
+#[test]
+fn bid_history_is_pruned_with_auction() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_eq!(AuctionsModule::bid_history(0).len(), 1);
+		run_to_block(20);
+		assert!(AuctionsModule::bid_history(0).is_empty());
+		assert_eq!(AuctionsModule::bid_history_bounds(0), (0, 0));
+		assert_eq!(AuctionsModule::bid_record(0, 0), None);
+	});
+}
+
+#[test]
+fn indexes_follow_auction_lifecycle() {
+	new_test_ext().execute_with(|| {
+		create_nfts(3);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				auction_type: AuctionType::Combinatorial,
+				bundle: vec![(0, 2)],
+				..english_auction((0, 1))
+			}
+		));
+		assert_eq!(AuctionsModule::auctions_by_owner(100, 0), Some(()));
+		assert_eq!(AuctionsModule::auctions_by_owner(100, 1), Some(()));
+		assert_eq!(AuctionsModule::auction_by_token(0, 0), Some(0));
+		assert_eq!(AuctionsModule::auction_by_token(0, 1), Some(1));
+		assert_eq!(AuctionsModule::auction_by_token(0, 2), Some(1));
+
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_ok!(AuctionsModule::bid_bundle(Origin::signed(200), 1, 0b11, 100));
+		assert_eq!(AuctionsModule::auctions_by_bidder(200, 0), Some(()));
+		assert_eq!(AuctionsModule::auctions_by_bidder(200, 1), Some(()));
+		// Outbid account no longer has a bid at stake
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 200));
+		assert_eq!(AuctionsModule::auctions_by_bidder(200, 0), None);
+		assert_eq!(AuctionsModule::auctions_by_bidder(300, 0), Some(()));
+
+		run_to_block(20);
+		for id in 0..2 {
+			assert_eq!(AuctionsModule::auctions_by_owner(100, id), None);
+			assert_eq!(AuctionsModule::auctions_by_bidder(200, id), None);
+			assert_eq!(AuctionsModule::auctions_by_bidder(300, id), None);
+		}
+		for token_id in 0..3 {
+			assert_eq!(AuctionsModule::auction_by_token(0, token_id), None);
+		}
+	});
+}
+
+#[test]
+fn indexes_are_cleared_on_removal_and_cancellation() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				auction_type: AuctionType::TopUp,
+				..english_auction((0, 1))
+			}
+		));
+		assert_ok!(AuctionsModule::delete_auction(Origin::signed(100), 0));
+		assert_eq!(AuctionsModule::auctions_by_owner(100, 0), None);
+		assert_eq!(AuctionsModule::auction_by_token(0, 0), None);
+
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 1, 100));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 1, 200));
+		assert_eq!(AuctionsModule::auctions_by_bidder(200, 1), Some(()));
+		assert_ok!(AuctionsModule::cancel_auction(Origin::signed(100), 1));
+		assert_eq!(AuctionsModule::auctions_by_owner(100, 1), None);
+		assert_eq!(AuctionsModule::auction_by_token(0, 1), None);
+		assert_eq!(AuctionsModule::auctions_by_bidder(200, 1), None);
+		assert_eq!(AuctionsModule::auctions_by_bidder(300, 1), None);
+	});
+}
+
+#[test]
+fn indexes_are_cleared_on_purchase() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			fixed_swap((0, 0), 500, 0)
+		));
+		assert_eq!(AuctionsModule::auction_by_token(0, 0), Some(0));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::buy(Origin::signed(200), 0));
+		assert_eq!(AuctionsModule::auctions_by_owner(100, 0), None);
+		assert_eq!(AuctionsModule::auction_by_token(0, 0), None);
+	});
+}
+
+#[test]
+fn active_auctions_are_paged_by_id() {
+	new_test_ext().execute_with(|| {
+		create_nfts(4);
+		for token_id in 0..4 {
+			assert_ok!(AuctionsModule::create_auction(
+				Origin::signed(100),
+				english_auction((0, token_id))
+			));
+		}
+		assert_ok!(AuctionsModule::delete_auction(Origin::signed(100), 1));
+
+		let ids = |start, count| {
+			AuctionsModule::active_auctions(start, count)
+				.into_iter()
+				.map(|(id, _)| id)
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(ids(0, 2), vec![0]);
+		assert_eq!(ids(2, 2), vec![2, 3]);
+		assert_eq!(ids(3, 2), vec![3]);
+		assert!(ids(4, 2).is_empty());
+		let mut owner_auctions = AuctionsModule::owner_auctions(&100);
+		owner_auctions.sort_unstable();
+		assert_eq!(owner_auctions, vec![0, 2, 3]);
+	});
+}
+
+#[test]
+fn minimal_next_bid_and_time_remaining() {
+	new_test_ext().execute_with(|| {
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			fixed_swap((0, 1), 500, 0)
+		));
+		run_to_block(5);
+		assert_eq!(AuctionsModule::minimal_next_bid(0), Some(50));
+		assert_eq!(AuctionsModule::time_remaining(0), Some(15));
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		assert_eq!(AuctionsModule::minimal_next_bid(0), Some(110));
+
+		assert_eq!(AuctionsModule::minimal_next_bid(1), None);
+		assert_eq!(AuctionsModule::time_remaining(1), None);
+		assert_eq!(AuctionsModule::minimal_next_bid(2), None);
+	});
+}
+
+#[test]
+fn storage_deposit_follows_auction_size() {
+	new_test_ext().execute_with(|| {
+		AuctionDeposit::set(20, 1);
+		create_nft();
+		let auction = english_auction((0, 0));
+		let deposit = 20 + auction.encoded_size() as u128;
+		assert_ok!(AuctionsModule::create_auction(Origin::signed(100), auction.clone()));
+		assert_eq!(AuctionsModule::storage_deposits(0), deposit);
+		assert_eq!(Balances::reserved_balance(100), deposit);
+
+		assert_ok!(AuctionsModule::update_auction(
+			Origin::signed(100),
+			0,
+			AuctionInfo {
+				name: "Aukce1 updated".as_bytes().to_vec(),
+				..auction
+			}
+		));
+		assert_eq!(AuctionsModule::storage_deposits(0), deposit + 8);
+		assert_eq!(Balances::reserved_balance(100), deposit + 8);
+
+		assert_ok!(AuctionsModule::delete_auction(Origin::signed(100), 0));
+		assert_eq!(AuctionsModule::storage_deposits(0), 0);
+		assert_eq!(Balances::reserved_balance(100), 0);
+	});
+}
+
+#[test]
+fn storage_deposit_is_returned_on_conclusion() {
+	new_test_ext().execute_with(|| {
+		AuctionDeposit::set(20, 1);
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 100));
+		run_to_block(20);
+
+		assert_eq!(AuctionsModule::storage_deposits(0), 0);
+		assert_eq!(Balances::reserved_balance(100), 0);
+		assert_eq!(Balances::free_balance(100), 10_000 + 100);
+	});
+}
+
+#[test]
+fn auction_name_is_bounded() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_noop!(
+			AuctionsModule::create_auction(
+				Origin::signed(100),
+				AuctionInfo {
+					name: vec![b'a'; 17],
+					..english_auction((0, 0))
+				}
+			),
+			Error::<Test>::AuctionNameTooLong
+		);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				name: vec![b'a'; 16],
+				..english_auction((0, 0))
+			}
+		));
+	});
+}
+
+#[test]
+fn commission_is_charged_at_settlement() {
+	new_test_ext().execute_with(|| {
+		Commission::set(Permill::from_percent(10));
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			english_auction((0, 0))
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 6_000));
+		run_to_block(20);
+
+		let concluded = Event::pallet_auction(crate::Event::AuctionConcluded(0, 5_400, 600));
+		assert!(System::events().iter().any(|record| record.event == concluded));
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 5_400);
+		assert_eq!(Balances::free_balance(200), 10_000 - 6_000);
+		assert_eq!(Balances::free_balance(FEE_ACCOUNT), 600);
+	});
+}
+
+#[test]
+fn commission_is_charged_on_purchases() {
+	new_test_ext().execute_with(|| {
+		Commission::set(Permill::from_percent(10));
+		create_nfts(2);
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			fixed_swap((0, 0), 5_000, 0)
+		));
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				buy_now_price: Some(1_000),
+				..english_auction((0, 1))
+			}
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::buy(Origin::signed(200), 0));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionSold(0, 200, 4_500, 500))
+		);
+		assert_ok!(AuctionsModule::buy(Origin::signed(300), 1));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::pallet_auction(crate::Event::AuctionBoughtNow(1, 300, 900, 100))
+		);
+
+		assert!(NFT::is_owner(&200, (0, 0)));
+		assert!(NFT::is_owner(&300, (0, 1)));
+		assert_eq!(Balances::free_balance(100), 10_000 + 4_500 + 900);
+		assert_eq!(Balances::free_balance(FEE_ACCOUNT), 600);
+	});
+}
+
+#[test]
+fn royalty_is_paid_on_secondary_sales() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(NFT::create_class(Origin::signed(100), "Class1".as_bytes().to_vec(), 0));
+		assert_ok!(NFT::set_class_royalty(
+			Origin::signed(100),
+			0,
+			Some(Royalty {
+				beneficiary: 500,
+				rate: Permill::from_percent(10)
+			})
+		));
+		assert_ok!(NFT::mint(
+			Origin::signed(100),
+			0,
+			"Class1_mint1".as_bytes().to_vec(),
+			TokenData { locked: false },
+			1
+		));
+		// The creator sells without paying royalty
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			fixed_swap((0, 0), 1_000, 0)
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::buy(Origin::signed(200), 0));
+		assert_eq!(Balances::free_balance(100), 10_000 + 1_000);
+		assert_eq!(Balances::free_balance(500), 10_000);
+
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(200),
+			AuctionInfo {
+				owner: 200,
+				start: 2,
+				end: 22,
+				..english_auction((0, 0))
+			}
+		));
+		run_to_block(3);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 1, 2_000));
+		run_to_block(22);
+
+		let royalty_paid = Event::pallet_auction(crate::Event::RoyaltyPaid(1, 500, 200));
+		assert!(System::events().iter().any(|record| record.event == royalty_paid));
+		let concluded = Event::pallet_auction(crate::Event::AuctionConcluded(1, 1_800, 0));
+		assert!(System::events().iter().any(|record| record.event == concluded));
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert_eq!(Balances::free_balance(200), 10_000 - 1_000 + 1_800);
+		assert_eq!(Balances::free_balance(500), 10_000 + 200);
+
+		// Fixed price resales pay the royalty too
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(300),
+			AuctionInfo {
+				owner: 300,
+				start: 22,
+				..fixed_swap((0, 0), 1_000, 0)
+			}
+		));
+		run_to_block(23);
+		assert_ok!(AuctionsModule::buy(Origin::signed(400), 2));
+		assert_eq!(Balances::free_balance(300), 10_000 - 2_000 + 900);
+		assert_eq!(Balances::free_balance(500), 10_000 + 300);
+	});
+}
+
+#[test]
+fn bids_in_a_token_leave_other_currencies_untouched() {
+	new_test_ext().execute_with(|| {
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				currency_id: Some(DOT),
+				..english_auction((0, 0))
+			}
+		));
+		run_to_block(2);
+		// Native funds don't back a bid in another currency
+		assert_noop!(
+			AuctionsModule::bid_value(Origin::signed(400), 0, 6_000),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(200), 0, 6_000));
+		assert_eq!(Tokens::reserved_balance(DOT, &200), 6_000);
+		assert_eq!(Tokens::free_balance(KSM, &200), 10_000);
+		assert_eq!(Balances::free_balance(200), 10_000);
+
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 0, 7_000));
+		assert_eq!(Tokens::free_balance(DOT, &200), 10_000);
+		run_to_block(20);
+
+		assert!(NFT::is_owner(&300, (0, 0)));
+		assert_eq!(Tokens::free_balance(DOT, &100), 7_000);
+		assert_eq!(Tokens::free_balance(DOT, &300), 10_000 - 7_000);
+		assert_eq!(Tokens::reserved_balance(DOT, &300), 0);
+		assert_eq!(Balances::free_balance(100), 10_000);
+		assert_eq!(Balances::free_balance(300), 10_000);
+	});
+}
+
+#[test]
+fn commission_in_a_token_goes_to_the_fee_account() {
+	new_test_ext().execute_with(|| {
+		Commission::set(Permill::from_percent(10));
+		create_nft();
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(100),
+			AuctionInfo {
+				currency_id: Some(DOT),
+				..fixed_swap((0, 0), 1_000, 0)
+			}
+		));
+		run_to_block(2);
+		assert_ok!(AuctionsModule::buy(Origin::signed(200), 0));
+		assert_eq!(Tokens::free_balance(DOT, &100), 900);
+		assert_eq!(Tokens::free_balance(DOT, &200), 10_000 - 1_000);
+		assert_eq!(Tokens::free_balance(DOT, &FEE_ACCOUNT), 100);
+
+		assert_ok!(AuctionsModule::create_auction(
+			Origin::signed(200),
+			AuctionInfo {
+				owner: 200,
+				start: 2,
+				end: 22,
+				currency_id: Some(DOT),
+				..english_auction((0, 0))
+			}
+		));
+		run_to_block(3);
+		assert_ok!(AuctionsModule::bid_value(Origin::signed(300), 1, 2_000));
+		run_to_block(22);
+
+		let concluded = Event::pallet_auction(crate::Event::AuctionConcluded(1, 1_800, 200));
+		assert!(System::events().iter().any(|record| record.event == concluded));
+		assert_eq!(Tokens::free_balance(DOT, &200), 10_000 - 1_000 + 1_800);
+		assert_eq!(Tokens::free_balance(DOT, &FEE_ACCOUNT), 100 + 200);
+		assert_eq!(Tokens::total_issuance(DOT), 20_000);
+		assert_eq!(Balances::free_balance(FEE_ACCOUNT), 0);
+	});
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(100, 10_000),
+			(200, 10_000),
+			(300, 10_000),
+			(400, 10_000),
+			(500, 10_000),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	orml_tokens::GenesisConfig::<Test> {
+		endowed_accounts: vec![(200, DOT, 10_000), (300, DOT, 10_000), (200, KSM, 10_000)],
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
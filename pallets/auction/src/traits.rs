@@ -4,8 +4,8 @@ use frame_support::{
 	Parameter,
 };
 use sp_runtime::{
-	traits::{AtLeast32Bit, Bounded, MaybeDisplay, MaybeSerializeDeserialize, Member},
-	RuntimeDebug,
+	traits::{AtLeast32Bit, Bounded, MaybeDisplay, MaybeSerializeDeserialize, Member, One},
+	Permill, RuntimeDebug,
 };
 use sp_std::{
 	fmt::{Debug, Display, Formatter},
@@ -14,12 +14,16 @@ use sp_std::{
 };
 
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum AuctionType {
 	English,
 	Candle,
 	Dutch,
 	TopUp,
 	FixedSwap,
+	SealedFirstPrice,
+	Vickrey,
+	Combinatorial,
 }
 
 impl Display for AuctionType {
@@ -34,22 +38,101 @@ impl Default for AuctionType {
 	}
 }
 
-#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq)]
-pub struct AuctionInfo<AccountId, Balance, BlockNumber, NftClassId, NFtTokenId> {
+/// Schedule by which the price of a Dutch auction drops
+#[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum PriceDecay<Balance, BlockNumber> {
+	/// Price drops by the amount every block
+	Linear(Balance),
+	/// Price drops by `amount` once every `blocks` blocks
+	Stepped { amount: Balance, blocks: BlockNumber },
+}
+
+impl<Balance: Copy, BlockNumber: Copy + One> PriceDecay<Balance, BlockNumber> {
+	/// Price drop and the number of blocks between two drops
+	pub fn rate(&self) -> (Balance, BlockNumber) {
+		match *self {
+			PriceDecay::Linear(amount) => (amount, One::one()),
+			PriceDecay::Stepped { amount, blocks } => (amount, blocks),
+		}
+	}
+}
+
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AuctionInfo<AccountId, Balance, BlockNumber, NftClassId, NFtTokenId, CurrencyId> {
 	pub name: Vec<u8>,
 	pub last_bid: Option<(AccountId, Balance)>,
 	pub start: BlockNumber,
+	/// Fixed swaps may leave the end at zero to never expire
 	pub end: BlockNumber,
 	pub owner: AccountId,
 	pub auction_type: AuctionType,
 	pub token_id: (NftClassId, NFtTokenId),
 	pub minimal_bid: Balance,
+	/// English and candle auctions only: the token is not sold if the winning bid is lower
+	pub reserve_price: Option<Balance>,
+	/// English auctions only: price at which the token can be bought right away while bids are lower
+	pub buy_now_price: Option<Balance>,
+	/// Candle auctions only: number of blocks before `end` in which the auction may be retroactively closed
+	pub ending_period: Option<BlockNumber>,
+	/// Dutch auctions: price at the start of the auction. Fixed swaps: the listing price
+	pub starting_price: Balance,
+	/// Dutch auctions only: lowest price, the auction is cancelled once it is reached
+	pub floor_price: Balance,
+	/// Dutch auctions only: how the price drops from `starting_price` to `floor_price`
+	pub price_decay: Option<PriceDecay<Balance, BlockNumber>>,
+	/// Top-up auctions only: receives the top-up fees instead of the owner
+	pub beneficiary: Option<AccountId>,
+	/// Sealed auctions only: number of blocks before `end` in which committed bids are revealed
+	pub reveal_period: Option<BlockNumber>,
+	/// Combinatorial auctions only: tokens auctioned together with `token_id`
+	pub bundle: Vec<(NftClassId, NFtTokenId)>,
+	/// English auctions only: the auction ends once no better bid arrives within this many blocks
+	pub inactivity_window: Option<BlockNumber>,
+	/// How much the next bid has to be raised in percent, instead of the runtime default
+	pub bid_step: Option<Permill>,
+	/// Smallest amount by which the next bid has to be raised, whatever the percentage
+	pub min_bid_increment: Option<Balance>,
+	/// Number of blocks before the end in which a bid postpones it, instead of the runtime default
+	pub anti_snipe_blocks: Option<BlockNumber>,
+	/// Currency of the bids and of the sale, the native currency unless set
+	pub currency_id: Option<CurrencyId>,
 	// pub no_identity_allowed: bool,
-	// pub starting_price: Balance,
 	// pub private: bool,
 	// pub max_participants: u32,
 }
 
+/// Accepted bid kept in the bid history of an auction
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BidRecord<AccountId, Balance, BlockNumber> {
+	pub bidder: AccountId,
+	pub amount: Balance,
+	/// Block in which the bid was placed
+	pub block: BlockNumber,
+}
+
+/// Bid committed to a sealed auction
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct SealedBid<Hash, Balance, BlockNumber> {
+	/// Hash of the encoded `(amount, salt)` pair
+	pub commitment: Hash,
+	/// Revealed amount and the block in which it was revealed
+	pub revealed: Option<(Balance, BlockNumber)>,
+}
+
+/// Bid on a subset of the items of a combinatorial auction, all or nothing
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct BundleBid<AccountId, Balance> {
+	pub bidder: AccountId,
+	/// Bitmask of the items, bit 0 is `token_id` and bit `n` is the `n`-th token of `bundle`
+	pub items: u32,
+	pub amount: Balance,
+}
+
 /// Abstraction over a NFT auction system.
 pub trait Auction<AccountId, BlockNumber, NftClassId, NftTokenId> {
 	/// The id of an AuctionInfo
@@ -58,15 +141,17 @@ pub trait Auction<AccountId, BlockNumber, NftClassId, NftTokenId> {
 	type Balance: AtLeast32Bit + Copy + MaybeSerializeDeserialize + Debug + Default;
 	/// Account id
 	type AccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
+	/// Currency an auction can be held in besides the native one
+	type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord;
 
 	/// Create new auction with specific startblock and endblock, return the id
 	fn new_auction(
-		info: AuctionInfo<Self::AccountId, Self::Balance, BlockNumber, NftClassId, NftTokenId>,
+		info: AuctionInfo<Self::AccountId, Self::Balance, BlockNumber, NftClassId, NftTokenId, Self::CurrencyId>,
 	) -> result::Result<Self::AuctionId, DispatchError>;
 	/// Update the auction info of `id` with `info`
 	fn update_auction(
 		id: Self::AuctionId,
-		info: AuctionInfo<Self::AccountId, Self::Balance, BlockNumber, NftClassId, NftTokenId>,
+		info: AuctionInfo<Self::AccountId, Self::Balance, BlockNumber, NftClassId, NftTokenId, Self::CurrencyId>,
 	) -> DispatchResult;
 	/// Remove auction by `id`
 	fn remove_auction(id: Self::AuctionId) -> DispatchResult;
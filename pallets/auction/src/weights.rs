@@ -10,13 +10,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_auction
+//! Weights for pallet_auction
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
-//! DATE: 2021-04-30, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//! THESE ARE NOT BENCHMARK RESULTS. Until the benchmarks are run on reference hardware with the
+//! command below, every weight is a conservative bound derived from the storage accessed by the
+//! worst case path of its call, listed above each function as `Storage:` lines:
+//!
+//! - every storage entry read counts as one DB read and every entry written as one DB write, an
+//!   entry both read and written counts as both,
+//! - an account whose balance changes counts as a read and a write of `System Account`, or of
+//!   `Tokens Accounts` for a bid in a token,
+//! - clearing a storage prefix counts as a write for every entry it can hold, except for the bid
+//!   history whose records are weighed one by one by the pallet,
+//! - the execution time is 15 µs for each DB access counted, above the 13.3 µs per access of the last
+//!   generated `bid_value` weight.
+//!
+//! `settle_bundle` is not linear in its components, the term the CLI reports for `m` has to be
+//! replaced by the `2^m * b` candidate checks of the bundle allocation search.
 
-// Executed Command:
+// Regenerate with:
 // target/release/subauction
 // benchmark
 // --chain=dev
@@ -42,47 +54,496 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_auction.
 pub trait WeightInfo {
-	fn create_auction() -> Weight;
+	fn create_auction(m: u32) -> Weight;
 	fn bid_value() -> Weight;
-	fn delete_auction() -> Weight;
+	fn delete_auction(m: u32) -> Weight;
+	fn buy() -> Weight;
+	fn commit_bid() -> Weight;
+	fn reveal_bid() -> Weight;
+	fn bid_bundle() -> Weight;
+	fn retry_settlement() -> Weight;
+	fn abort_settlement() -> Weight;
+	fn on_initialize(c: u32) -> Weight;
+	fn settle_bids(b: u32) -> Weight;
+	fn settle_candle(s: u32) -> Weight;
+	fn settle_bundle(m: u32, b: u32) -> Weight;
+	fn pay_royalties(r: u32) -> Weight;
+	fn update_auction() -> Weight;
+	fn close_auction() -> Weight;
+	fn cancel_auction() -> Weight;
+	fn commit_reserve() -> Weight;
+	fn reveal_reserve() -> Weight;
 }
 
 /// Weights for pallet_auction using the subauction node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	fn create_auction() -> Weight {
-		(74_809_000 as Weight)
+	// Storage: Auctions NextAuctionId (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:0 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Auctions Auctions (r:0 w:1)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:1)
+	// Storage: OrmlNft TokensByOwner (r:1 w:0)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	fn create_auction(m: u32) -> Weight {
+		(195_000_000 as Weight)
+			.saturating_add((75_000_000 as Weight).saturating_mul(m as Weight))
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
 	}
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions CandleBidders (r:3 w:3)
+	// Storage: Auctions CandleSnapshots (r:2 w:2)
+	// Storage: Auctions ReservedBids (r:2 w:2)
+	// Storage: Auctions AuctionsByBidder (r:0 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions BidHistory (r:0 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:2)
 	fn bid_value() -> Weight {
-		(66_639_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+		(435_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions BidHistory (r:0 w:1)
+	// Storage: Auctions BidHistoryBounds (r:0 w:1)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:1 w:1)
+	fn delete_auction(m: u32) -> Weight {
+		(240_000_000 as Weight)
+			.saturating_add((60_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
+	}
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:1)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: System Account (r:5 w:5)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: OrmlNft TokensByOwner (r:0 w:2)
+	// Storage: Nft ClassRoyalties (r:1 w:0)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:1 w:1)
+	fn buy() -> Weight {
+		(510_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(20 as Weight))
+	}
+	// Storage: Auctions Auctions (r:1 w:0)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions SealedBidCount (r:1 w:1)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn commit_bid() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Auctions Auctions (r:1 w:0)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions BidHistory (r:0 w:1)
+	fn reveal_bid() -> Weight {
+		(165_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Auctions Auctions (r:1 w:0)
+	// Storage: Auctions BundleBids (r:1 w:1)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions BidHistory (r:0 w:1)
+	fn bid_bundle() -> Weight {
+		(165_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Auctions FailedSettlements (r:1 w:1)
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: OrmlNft TokensByOwner (r:0 w:2)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: Auctions TopUpBids (r:1 w:1)
+	// Storage: Auctions SealedBidCount (r:0 w:1)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions CandleSnapshots (r:1 w:1)
+	// Storage: Auctions CandleBidders (r:0 w:1)
+	fn retry_settlement() -> Weight {
+		(585_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(24 as Weight))
+	}
+	// Storage: Auctions FailedSettlements (r:1 w:1)
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: Auctions TopUpBids (r:1 w:1)
+	// Storage: Auctions SealedBidCount (r:0 w:1)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions BundleBids (r:1 w:1)
+	// Storage: Auctions CandleSnapshots (r:1 w:1)
+	// Storage: Auctions CandleBidders (r:0 w:1)
+	fn abort_settlement() -> Weight {
+		(555_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(22 as Weight))
+	}
+	// Storage: Auctions ConclusionQueueBounds (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:1 w:1)
+	// Storage: Auctions ConclusionQueue (r:1 w:1)
+	// Storage: Auctions FailedSettlements (r:0 w:1)
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: OrmlNft TokensByOwner (r:0 w:2)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: Auctions TopUpBids (r:1 w:1)
+	// Storage: Auctions SealedBidCount (r:0 w:1)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions CandleSnapshots (r:1 w:1)
+	// Storage: Auctions CandleBidders (r:0 w:1)
+	fn on_initialize(c: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((600_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((15 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((25 as Weight).saturating_mul(c as Weight)))
 	}
-	fn delete_auction() -> Weight {
-		(62_980_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+	// Storage: Auctions TopUpBids (r:1 w:1)
+	// Storage: Auctions SealedBidCount (r:0 w:1)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn settle_bids(b: u32) -> Weight {
+		(75_000_000 as Weight)
+			.saturating_add((105_000_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(b as Weight)))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(b as Weight)))
+	}
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Auctions CandleSnapshots (r:1 w:1)
+	// Storage: Auctions CandleBidders (r:0 w:1)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn settle_candle(s: u32) -> Weight {
+		(135_000_000 as Weight)
+			.saturating_add((120_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: Auctions BundleBids (r:1 w:1)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: OrmlNft TokensByOwner (r:0 w:2)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	fn settle_bundle(m: u32, b: u32) -> Weight {
+		let candidates = (1 as Weight)
+			.checked_shl(m)
+			.unwrap_or(Weight::max_value())
+			.saturating_mul(b as Weight);
+		(30_000_000 as Weight)
+			.saturating_add((1_000_000 as Weight).saturating_mul(candidates))
+			.saturating_add((135_000_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((90_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(m as Weight)))
+	}
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: Nft ClassRoyalties (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn pay_royalties(r: u32) -> Weight {
+		(0 as Weight)
+			.saturating_add((90_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:2)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn update_auction() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Auctions FailedSettlements (r:0 w:1)
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: OrmlNft TokensByOwner (r:0 w:2)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: Auctions TopUpBids (r:1 w:1)
+	// Storage: Auctions SealedBidCount (r:0 w:1)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions CandleSnapshots (r:1 w:1)
+	// Storage: Auctions CandleBidders (r:0 w:1)
+	fn close_auction() -> Weight {
+		(570_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(24 as Weight))
+	}
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions AuctionEndById (r:1 w:1)
+	// Storage: Auctions AuctionEndTime (r:0 w:1)
+	// Storage: OrmlNft Classes (r:1 w:0)
+	// Storage: OrmlNft Tokens (r:1 w:1)
+	// Storage: Auctions AuctionOwnerById (r:0 w:1)
+	// Storage: Auctions AuctionsByOwner (r:0 w:1)
+	// Storage: Auctions AuctionByToken (r:0 w:1)
+	// Storage: Auctions BidHistoryBounds (r:1 w:1)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: Auctions SellerDeposits (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Auctions ReservedBids (r:1 w:1)
+	// Storage: Auctions AuctionsByBidder (r:0 w:1)
+	// Storage: Auctions TopUpBids (r:1 w:1)
+	// Storage: Auctions SealedBidCount (r:0 w:1)
+	// Storage: Auctions SealedBids (r:1 w:1)
+	// Storage: Auctions BundleBids (r:1 w:1)
+	// Storage: Auctions CandleSnapshots (r:1 w:1)
+	// Storage: Auctions CandleBidders (r:0 w:1)
+	fn cancel_auction() -> Weight {
+		(525_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
+	}
+	// Storage: Auctions Auctions (r:1 w:0)
+	// Storage: Auctions ReserveCommitments (r:0 w:1)
+	fn commit_reserve() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Auctions Auctions (r:1 w:1)
+	// Storage: Auctions ReserveCommitments (r:1 w:1)
+	// Storage: Auctions StorageDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reveal_reserve() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	fn create_auction() -> Weight {
-		(74_809_000 as Weight)
+	fn create_auction(m: u32) -> Weight {
+		(195_000_000 as Weight)
+			.saturating_add((75_000_000 as Weight).saturating_mul(m as Weight))
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
 	}
 	fn bid_value() -> Weight {
-		(66_639_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+		(435_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+	}
+	fn delete_auction(m: u32) -> Weight {
+		(240_000_000 as Weight)
+			.saturating_add((60_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
+	}
+	fn buy() -> Weight {
+		(510_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(20 as Weight))
+	}
+	fn commit_bid() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
-	fn delete_auction() -> Weight {
-		(62_980_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+	fn reveal_bid() -> Weight {
+		(165_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn bid_bundle() -> Weight {
+		(165_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn retry_settlement() -> Weight {
+		(585_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(24 as Weight))
+	}
+	fn abort_settlement() -> Weight {
+		(555_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(22 as Weight))
+	}
+	fn on_initialize(c: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((600_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((15 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((25 as Weight).saturating_mul(c as Weight)))
+	}
+	fn settle_bids(b: u32) -> Weight {
+		(75_000_000 as Weight)
+			.saturating_add((105_000_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(b as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(b as Weight)))
+	}
+	fn settle_candle(s: u32) -> Weight {
+		(135_000_000 as Weight)
+			.saturating_add((120_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(s as Weight)))
+	}
+	fn settle_bundle(m: u32, b: u32) -> Weight {
+		let candidates = (1 as Weight)
+			.checked_shl(m)
+			.unwrap_or(Weight::max_value())
+			.saturating_mul(b as Weight);
+		(30_000_000 as Weight)
+			.saturating_add((1_000_000 as Weight).saturating_mul(candidates))
+			.saturating_add((135_000_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((90_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(m as Weight)))
+	}
+	fn pay_royalties(r: u32) -> Weight {
+		(0 as Weight)
+			.saturating_add((90_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	fn update_auction() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn close_auction() -> Weight {
+		(570_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(24 as Weight))
+	}
+	fn cancel_auction() -> Weight {
+		(525_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(21 as Weight))
+	}
+	fn commit_reserve() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_reserve() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 }
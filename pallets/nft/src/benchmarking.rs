@@ -67,6 +67,16 @@ benchmarks! {
 	}: _(RawOrigin::Signed(caller.clone()), token)
 	verify {
 	}
+
+	set_class_royalty {
+		let caller = create_account::<T>("caller", 0);
+		let class_metadata = "just a token class".as_bytes().to_vec();
+		let class_data = 123;
+		let class_id = orml_nft::Module::<T>::create_class(&caller, class_metadata, class_data).unwrap_or_default();
+		let royalty = Royalty { beneficiary: caller.clone(), rate: Permill::from_percent(5) };
+	}: _(RawOrigin::Signed(caller.clone()), class_id, Some(royalty))
+	verify {
+	}
 }
 
 #[cfg(test)]
@@ -90,6 +100,7 @@ mod tests {
 			assert_ok!(test_benchmark_transfer::<Test>());
 			assert_ok!(test_benchmark_burn::<Test>());
 			assert_ok!(test_benchmark_destroy_class::<Test>());
+			assert_ok!(test_benchmark_set_class_royalty::<Test>());
 		});
 	}
 }
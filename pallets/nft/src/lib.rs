@@ -10,7 +10,7 @@ use frame_support::{
 use frame_system::ensure_signed;
 use sp_runtime::{
 	traits::{StaticLookup, Zero},
-	RuntimeDebug,
+	Permill, RuntimeDebug,
 };
 use sp_std::vec::Vec;
 use weights::WeightInfo;
@@ -35,6 +35,14 @@ pub struct TokenData {
 	pub locked: bool,
 }
 
+/// Share of every secondary sale of the tokens of a class paid to the beneficiary
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct Royalty<AccountId> {
+	pub beneficiary: AccountId,
+	pub rate: Permill,
+}
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
@@ -51,6 +59,9 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + orml_nft::Config<ClassData = ClassData, TokenData = TokenData> {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type WeightInfo: WeightInfo;
+		/// Highest royalty a class can declare, it has to leave room for the commission of a sale
+		#[pallet::constant]
+		type MaxRoyalty: Get<Permill>;
 	}
 
 	#[pallet::call]
@@ -123,14 +134,48 @@ pub mod pallet {
 				Error::<T>::CannotDestroyClass
 			);
 			orml_nft::Module::<T>::destroy_class(&sender, class_id)?;
+			ClassRoyalties::<T>::remove(class_id);
 			Self::deposit_event(Event::NFTTokenClassDestroyed(sender, class_id));
 			Ok(().into())
 		}
+
+		/// Declare the royalty of a class, possible only while none of its tokens exist
+		#[pallet::weight(<T as Config>::WeightInfo::set_class_royalty())]
+		pub fn set_class_royalty(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			royalty: Option<Royalty<T::AccountId>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassNotFound)?;
+			ensure!(sender == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				class_info.total_issuance == Zero::zero(),
+				Error::<T>::CannotChangeRoyalty
+			);
+			ensure!(
+				royalty
+					.as_ref()
+					.map_or(true, |royalty| royalty.rate <= T::MaxRoyalty::get()),
+				Error::<T>::RoyaltyTooHigh
+			);
+			match royalty {
+				Some(royalty) => ClassRoyalties::<T>::insert(class_id, royalty),
+				None => ClassRoyalties::<T>::remove(class_id),
+			}
+			Self::deposit_event(Event::NFTClassRoyaltySet(sender, class_id));
+			Ok(().into())
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
 
+	#[pallet::storage]
+	#[pallet::getter(fn class_royalty)]
+	/// Royalty of each class which declared one
+	pub type ClassRoyalties<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, Royalty<T::AccountId>, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -140,6 +185,7 @@ pub mod pallet {
 		NFTTokenTransferred(T::AccountId, T::AccountId, T::ClassId, T::TokenId),
 		NFTTokenBurned(T::AccountId, T::ClassId, T::TokenId),
 		NFTTokenClassDestroyed(T::AccountId, T::ClassId),
+		NFTClassRoyaltySet(T::AccountId, T::ClassId),
 	}
 
 	#[pallet::error]
@@ -150,6 +196,8 @@ pub mod pallet {
 		CannotDestroyClass,
 		TokenLocked,
 		InvalidQuantity,
+		CannotChangeRoyalty,
+		RoyaltyTooHigh,
 	}
 }
 
@@ -158,6 +206,12 @@ impl<T: Config> Pallet<T> {
 		orml_nft::Module::<T>::is_owner(account, token)
 	}
 
+	/// Royalty due when `seller` sells a token of the class, none on sales by its creator or beneficiary
+	pub fn secondary_sale_royalty(seller: &T::AccountId, class_id: T::ClassId) -> Option<Royalty<T::AccountId>> {
+		let class_info = orml_nft::Module::<T>::classes(class_id)?;
+		Self::class_royalty(class_id).filter(|royalty| class_info.owner != *seller && royalty.beneficiary != *seller)
+	}
+
 	pub fn is_locked(token: (T::ClassId, T::TokenId)) -> Result<bool, DispatchError> {
 		let token_info = orml_nft::Module::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenNotFound)?;
 		Ok(token_info.data.locked)
@@ -6,7 +6,7 @@ use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
-	Perbill,
+	Perbill, Permill,
 };
 
 mod nfc {
@@ -27,14 +27,19 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
 		OrmlNft: orml_nft::{Module, Storage},
-		Nft: pallet_nft::{Module, Call, Event<T>},
+		Nft: pallet_nft::{Module, Call, Storage, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
+parameter_types! {
+	pub const MaxRoyalty: Permill = Permill::from_percent(50);
+}
+
 impl pallet_nft::Config for Test {
 	type Event = Event;
 	type WeightInfo = pallet_nft::weights::SubstrateWeight<Test>;
+	type MaxRoyalty = MaxRoyalty;
 }
 
 impl orml_nft::Config for Test {
@@ -205,3 +205,73 @@ fn destroy_class_fails() {
 		);
 	});
 }
+
+#[test]
+fn set_class_royalty_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NftModule::create_class(
+			Origin::signed(ALICE),
+			"a class".as_bytes().to_vec(),
+			Default::default()
+		));
+		let royalty = Royalty {
+			beneficiary: BOB,
+			rate: Permill::from_percent(5),
+		};
+
+		assert_ok!(NftModule::set_class_royalty(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			Some(royalty.clone())
+		));
+		let event = Event::pallet_nft(crate::Event::NFTClassRoyaltySet(ALICE, CLASS_ID));
+		assert_eq!(last_event(), event);
+		assert_eq!(NftModule::class_royalty(CLASS_ID), Some(royalty));
+
+		assert_ok!(NftModule::set_class_royalty(Origin::signed(ALICE), CLASS_ID, None));
+		assert_eq!(NftModule::class_royalty(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn set_class_royalty_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NftModule::create_class(
+			Origin::signed(ALICE),
+			"a class".as_bytes().to_vec(),
+			Default::default()
+		));
+		let royalty = Royalty {
+			beneficiary: BOB,
+			rate: Permill::from_percent(5),
+		};
+
+		assert_noop!(
+			NftModule::set_class_royalty(Origin::signed(BOB), CLASS_ID, Some(royalty.clone())),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NftModule::set_class_royalty(
+				Origin::signed(ALICE),
+				CLASS_ID,
+				Some(Royalty {
+					beneficiary: BOB,
+					rate: Permill::from_percent(60),
+				})
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+
+		assert_ok!(NftModule::mint(
+			Origin::signed(ALICE),
+			0,
+			"a token".as_bytes().to_vec(),
+			TokenData { locked: false },
+			TEST_QUANTITY,
+		));
+		assert_noop!(
+			NftModule::set_class_royalty(Origin::signed(ALICE), CLASS_ID, Some(royalty)),
+			Error::<Test>::CannotChangeRoyalty
+		);
+	});
+}
@@ -47,6 +47,7 @@ pub trait WeightInfo {
 	fn transfer() -> Weight;
 	fn destroy_class() -> Weight;
 	fn burn() -> Weight;
+	fn set_class_royalty() -> Weight;
 }
 
 /// Weights for pallet_nft using the subauction node and recommended hardware.
@@ -78,6 +79,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	fn set_class_royalty() -> Weight {
+		(24_615_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -107,4 +113,9 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	fn set_class_royalty() -> Weight {
+		(24_615_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
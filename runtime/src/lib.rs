@@ -7,17 +7,20 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use orml_traits::parameter_type_with_key;
 use pallet_grandpa::fg_primitives;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 use pallet_transaction_payment::CurrencyAdapter;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
-use sp_runtime::traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify};
+use sp_runtime::traits::{
+	AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify, Zero,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	ApplyExtrinsicResult, ModuleId, MultiSignature,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -70,6 +73,21 @@ pub type Hash = sp_core::H256;
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
+/// Identifier of an auction.
+pub type AuctionId = u64;
+
+/// Identifier of a NFT class.
+pub type ClassId = u64;
+
+/// Identifier of a NFT within its class.
+pub type TokenId = u64;
+
+/// Identifier of a fungible token auctions can be held in besides the native currency.
+pub type CurrencyId = u32;
+
+/// Signed change of a token balance.
+pub type Amount = i128;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -98,10 +116,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("subauction"),
 	impl_name: create_runtime_str!("subauction"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 2,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -256,23 +274,94 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Zero::zero()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+parameter_types! {
+	// Together with the auction commission a sale never pays out more than its price
+	pub const MaxRoyalty: Permill = Permill::from_percent(50);
+}
+
 impl pallet_nft::Config for Runtime {
 	type Event = Event;
 	type WeightInfo = pallet_nft::weights::SubstrateWeight<Runtime>;
+	type MaxRoyalty = MaxRoyalty;
 }
 
 impl orml_nft::Config for Runtime {
-	type ClassId = u64;
-	type TokenId = u64;
+	type ClassId = ClassId;
+	type TokenId = TokenId;
 	type ClassData = u32;
 	type TokenData = pallet_nft::TokenData;
 }
 
+parameter_types! {
+	pub const SealedBidDeposit: Balance = 1_000;
+	pub const MaxBundleItems: u32 = 8;
+	pub const MaxBundleBids: u32 = 32;
+	pub const MaxTopUpBids: u32 = 100;
+	pub const MaxSealedBids: u32 = 100;
+	pub const MaxConclusionsPerBlock: u32 = 50;
+	pub const MaxCandleEndingPeriod: BlockNumber = 600;
+	pub const BidStep: Permill = Permill::from_percent(10);
+	pub const MinBidStep: Permill = Permill::from_percent(1);
+	pub const MaxBidStep: Permill = Permill::from_percent(50);
+	pub const AntiSnipeBlocks: BlockNumber = 10;
+	pub const MaxAntiSnipeBlocks: BlockNumber = 100;
+	pub const MinAuctionDuration: BlockNumber = 10;
+	pub const MaxBidHistory: u32 = 100;
+	pub const CancellationPenalty: Balance = 1_000;
+	pub const AuctionDepositBase: Balance = 1_000;
+	pub const AuctionDepositPerByte: Balance = 10;
+	pub const MaxNameLength: u32 = 128;
+	pub const Commission: Permill = Permill::from_percent(2);
+	pub const AuctionModuleId: ModuleId = ModuleId(*b"py/auctn");
+	pub TokenCommissionAccount: AccountId = AuctionModuleId::get().into_account();
+}
+
 impl pallet_auction::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
-	type AuctionId = u64;
+	type AuctionId = AuctionId;
 	type Currency = Balances;
+	type CurrencyId = CurrencyId;
+	type MultiCurrency = Tokens;
+	type Randomness = RandomnessCollectiveFlip;
+	type SealedBidDeposit = SealedBidDeposit;
+	type MaxBundleItems = MaxBundleItems;
+	type MaxBundleBids = MaxBundleBids;
+	type MaxTopUpBids = MaxTopUpBids;
+	type MaxSealedBids = MaxSealedBids;
+	type MaxConclusionsPerBlock = MaxConclusionsPerBlock;
+	type MaxCandleEndingPeriod = MaxCandleEndingPeriod;
+	type CancellationPenalty = CancellationPenalty;
+	type BidStep = BidStep;
+	type MinBidStep = MinBidStep;
+	type MaxBidStep = MaxBidStep;
+	type AntiSnipeBlocks = AntiSnipeBlocks;
+	type MaxAntiSnipeBlocks = MaxAntiSnipeBlocks;
+	type MinAuctionDuration = MinAuctionDuration;
+	type MaxBidHistory = MaxBidHistory;
+	type AuctionDepositBase = AuctionDepositBase;
+	type AuctionDepositPerByte = AuctionDepositPerByte;
+	type MaxNameLength = MaxNameLength;
+	type Commission = Commission;
+	// Without a treasury the native commission is burnt, the one in tokens is kept by the pallet account
+	type CommissionDestination = ();
+	type TokenCommissionAccount = TokenCommissionAccount;
 	type WeightInfo = pallet_auction::weights::SubstrateWeight<Runtime>;
 	type CurrencyBalance = Balance;
 }
@@ -291,10 +380,11 @@ construct_runtime!(
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
+		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
 
 		Auctions: pallet_auction::{Module, Call, Storage, Event<T>},
 		OrmlNft: orml_nft::{Module, Storage, Config<T>},
-		Nft: pallet_nft::{Module, Call, Event<T>},
+		Nft: pallet_nft::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -458,6 +548,41 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_auction_rpc_runtime_api::AuctionApi<Block, AccountId, AuctionId, Balance, BlockNumber, ClassId, TokenId, CurrencyId>
+		for Runtime {
+		fn auction(id: AuctionId) -> Option<pallet_auction::AuctionInfoOf<Runtime>> {
+			Auctions::auctions(id)
+		}
+
+		fn active_auctions(start: AuctionId, count: u32) -> Vec<(AuctionId, pallet_auction::AuctionInfoOf<Runtime>)> {
+			Auctions::active_auctions(start, count)
+		}
+
+		fn minimal_next_bid(id: AuctionId) -> Option<Balance> {
+			Auctions::minimal_next_bid(id)
+		}
+
+		fn current_price(id: AuctionId) -> Option<Balance> {
+			Auctions::current_price(id)
+		}
+
+		fn time_remaining(id: AuctionId) -> Option<BlockNumber> {
+			Auctions::time_remaining(id)
+		}
+
+		fn auctions_by_owner(owner: AccountId) -> Vec<AuctionId> {
+			Auctions::owner_auctions(&owner)
+		}
+
+		fn auction_by_token(class_id: ClassId, token_id: TokenId) -> Option<AuctionId> {
+			Auctions::auction_by_token(class_id, token_id)
+		}
+
+		fn bid_history(id: AuctionId) -> Vec<pallet_auction::BidRecordOf<Runtime>> {
+			Auctions::bid_history(id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(